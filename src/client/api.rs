@@ -2,14 +2,61 @@
 pub mod api_wrapper {
     use std::fmt::Debug;
 
-    use reqwest::{header, Client as ReqwestClient, Error, Method, RequestBuilder, Response, Url};
+    use futures::{Stream, StreamExt};
+    use reqwest::{header::{self, HeaderMap}, Client as ReqwestClient, Method, RequestBuilder, Response, Url};
     use serde::{de::DeserializeOwned, Serialize};
 
     use crate::{
-        api_client::{ApiError, Client, RequestError},
-        api_models::responses::ResponseModel,
+        api_client::{ApiError, AuthFailReason, Client, RequestError},
+        api_models::{
+            pagination::{Page, PageParams},
+            responses::{ErrorModel, PageEnvelope, ResponseModel},
+        },
     };
 
+    /// Splits a stream of byte chunks into newline-delimited JSON values, buffering partial lines across chunk boundaries.
+    /// Blank lines are skipped, and a failed line produces an [ApiError::ParseError] prefixed with its (1-based) line number.
+    pub(crate) fn ndjson_stream<T, B, S>(chunks: S) -> impl Stream<Item = Result<T, ApiError>>
+    where
+        T: DeserializeOwned,
+        B: AsRef<[u8]>,
+        S: Stream<Item = Result<B, ApiError>> + Unpin,
+    {
+        futures::stream::unfold(
+            (chunks, Vec::<u8>::new(), 0usize, false),
+            |(mut chunks, mut buffer, mut line_number, mut finished)| async move {
+                loop {
+                    let line = if let Some(position) = buffer.iter().position(|b| *b == b'\n') {
+                        buffer.drain(..=position).collect::<Vec<u8>>()
+                    } else if finished {
+                        if buffer.is_empty() {
+                            return None;
+                        }
+                        std::mem::take(&mut buffer)
+                    } else {
+                        match chunks.next().await {
+                            Some(Ok(chunk)) => buffer.extend_from_slice(chunk.as_ref()),
+                            Some(Err(e)) => return Some((Err(e), (chunks, Vec::new(), line_number, true))),
+                            None => finished = true,
+                        }
+                        continue;
+                    };
+
+                    line_number += 1;
+                    let text = String::from_utf8_lossy(&line).trim().to_string();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    let item = serde_json::from_str::<T>(text.as_str()).map_err(|e| ApiError::ParseError {
+                        text: format!("line {line_number}: {text}"),
+                        serde_error: e.to_string(),
+                    });
+                    return Some((item, (chunks, buffer, line_number, finished)));
+                }
+            },
+        )
+    }
+
     #[derive(Clone, Debug)]
     /// Wrapper struct for API, implements all API methods. Generally not useful for clients.
     pub struct Api {
@@ -23,8 +70,14 @@ pub mod api_wrapper {
         }
 
         /// Fetches the base URL from the [Client] instance
+        #[deprecated(since = "0.2.0", note = "Use base_url() instead")]
         pub fn base(&self) -> String {
-            self.client.url()
+            self.base_url().to_string()
+        }
+
+        /// Fetches the base URL from the [Client] instance without allocating
+        pub fn base_url(&self) -> &str {
+            self.client.base_url()
         }
 
         /// Fetches the API token from the [Client] instance
@@ -37,10 +90,19 @@ pub mod api_wrapper {
             self.client.is_authenticated()
         }
 
-        /// Assembles an API url from the base url and an endpoint.
+        /// Assembles an API url from the base url, the client's base path (if any), and an endpoint.
         pub fn url(&self, endpoint: &str) -> Result<Url, ApiError> {
-            if let Ok(result) = Url::parse(self.base().as_str()) {
-                if let Ok(result) = result.join(vec!["/api", endpoint].join("").as_str()) {
+            self.join_url(["/api", endpoint].join("").as_str())
+        }
+
+        /// Assembles a url for a web (non-API) route, such as `/me/export.json`, from the base url, the client's base path (if any), and a path.
+        pub fn web_url(&self, path: &str) -> Result<Url, ApiError> {
+            self.join_url(path)
+        }
+
+        fn join_url(&self, path: &str) -> Result<Url, ApiError> {
+            if let Ok(result) = Url::parse(self.base_url()) {
+                if let Ok(result) = result.join(vec![self.client.base_path(), path].join("").as_str()) {
                     Ok(result)
                 } else {
                     Err(ApiError::UrlError {})
@@ -50,36 +112,27 @@ pub mod api_wrapper {
             }
         }
 
-        fn http(&self) -> Result<ReqwestClient, Error> {
-            let mut headers = header::HeaderMap::new();
-            headers.insert(
-                "Accept",
-                header::HeaderValue::from_static("application/json"),
-            );
-            headers.insert(
-                "Content-Type",
-                header::HeaderValue::from_static("application/json"),
-            );
-
-            ReqwestClient::builder().default_headers(headers).build()
+        fn http(&self) -> ReqwestClient {
+            self.client.http()
         }
 
         /// Assembles a request builder with default settings
         pub fn request(&self, endpoint: &str, method: Method) -> Result<RequestBuilder, ApiError> {
-            if let Ok(http) = self.http() {
-                if let Ok(url) = self.url(endpoint) {
-                    let mut request = http.request(method, url.clone());
-                    println!("{:?}", url);
-                    if let Some(token) = self.token() {
-                        request = request.header(header::AUTHORIZATION, format!("Token {token}"));
-                    }
-                    Ok(request)
-                } else {
-                    Err(ApiError::UrlError {})
-                }
-            } else {
-                Err(ApiError::UnknownError {})
+            self.authorized_request(self.url(endpoint)?, method)
+        }
+
+        /// Assembles a request builder for a web (non-API) route, authenticated like API requests
+        pub fn web_request(&self, path: &str, method: Method) -> Result<RequestBuilder, ApiError> {
+            self.authorized_request(self.web_url(path)?, method)
+        }
+
+        fn authorized_request(&self, url: Url, method: Method) -> Result<RequestBuilder, ApiError> {
+            let mut request = self.http().request(method, url.clone());
+            println!("{:?}", url);
+            if let Some(token) = self.token() {
+                request = request.header(header::AUTHORIZATION, format!("Token {token}"));
             }
+            Ok(request)
         }
 
         /// Extracts a reponse with serde
@@ -87,28 +140,104 @@ pub mod api_wrapper {
             &self,
             response: Response,
         ) -> Result<T, ApiError> {
-            match response.error_for_status() {
-                Ok(resp) => {
-                    let text = resp.text().await.unwrap();
-                    serde_json::from_str::<ResponseModel>(text.clone().as_str())
-                        .or(Err(ApiError::ParseError {
-                            text: text.clone(),
-                        }))
-                        .and_then(|v| {
-                            serde_json::from_value::<T>(v.data).or(Err(ApiError::ParseError {
-                                text: text.clone(),
-                            }))
-                        })
-                }
-                Err(resp) => Err(ApiError::Request {
+            if !response.status().is_success() {
+                return Err(self.error_response(response).await);
+            }
+            let text = response.text().await.unwrap();
+            serde_json::from_str::<ResponseModel>(text.clone().as_str())
+                .map_err(|e| ApiError::ParseError {
+                    text: text.clone(),
+                    serde_error: e.to_string(),
+                })
+                .and_then(|v| {
+                    serde_json::from_value::<T>(v.data).map_err(|e| ApiError::ParseError {
+                        text: text.clone(),
+                        serde_error: e.to_string(),
+                    })
+                })
+        }
+
+        /// Converts an unsuccessful response into an [ApiError], parsing the server's error message to detect authentication failures
+        async fn error_response(&self, response: Response) -> ApiError {
+            let status = response.status();
+            let error = response.error_for_status_ref().err();
+            let message = response
+                .text()
+                .await
+                .ok()
+                .and_then(|text| serde_json::from_str::<ErrorModel>(text.as_str()).ok())
+                .and_then(|e| e.error_msg)
+                .unwrap_or_default();
+            match AuthFailReason::from_response(status.as_u16(), message.as_str()) {
+                Some(reason) => ApiError::Unauthorized { reason },
+                None => ApiError::Request {
                     error: RequestError {
-                        code: resp.status().map_or(0, |s| s.as_u16()),
-                        reason: Some(resp.to_string()),
+                        code: status.as_u16(),
+                        reason: error.map(|e| e.to_string()),
                     },
-                }),
+                },
+            }
+        }
+
+        /// Checks the status of a response that isn't expected to carry any data
+        pub async fn extract_empty(&self, response: Response) -> Result<(), ApiError> {
+            if response.status().is_success() {
+                Ok(())
+            } else {
+                Err(self.error_response(response).await)
+            }
+        }
+
+        /// Executes a GET request for an endpoint that doesn't wrap its response in the standard `{"code", "data"}` envelope
+        pub async fn get_raw<T: DeserializeOwned + Debug>(
+            &self,
+            endpoint: &str,
+        ) -> Result<T, ApiError> {
+            if let Ok(response) = self.request(endpoint, Method::GET)?.send().await {
+                if !response.status().is_success() {
+                    return Err(self.error_response(response).await);
+                }
+                let text = response.text().await.or(Err(ApiError::ConnectionError {}))?;
+                serde_json::from_str::<T>(text.as_str()).map_err(|e| ApiError::ParseError { text, serde_error: e.to_string() })
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
+
+        /// Executes a GET request against a web (non-API) route, returning the raw response body
+        pub async fn get_web_bytes(&self, path: &str) -> Result<Vec<u8>, ApiError> {
+            if let Ok(mut response) = self.web_request(path, Method::GET)?.send().await {
+                if !response.status().is_success() {
+                    return Err(self.error_response(response).await);
+                }
+                let mut bytes = Vec::new();
+                while let Some(chunk) = response.chunk().await.or(Err(ApiError::ConnectionError {}))? {
+                    bytes.extend_from_slice(&chunk);
+                }
+                Ok(bytes)
+            } else {
+                Err(ApiError::ConnectionError {})
             }
         }
 
+        /// Executes a GET request for an endpoint returning newline-delimited JSON, yielding each line as it arrives
+        pub fn stream_get<T: DeserializeOwned + Debug>(&self, endpoint: &str) -> impl Stream<Item = Result<T, ApiError>> {
+            let api = self.clone();
+            let request = self.request(endpoint, Method::GET);
+            futures::stream::once(async move {
+                let response = request?.send().await.or(Err(ApiError::ConnectionError {}))?;
+                if response.status().is_success() {
+                    Ok(response.bytes_stream().map(|chunk| chunk.or(Err(ApiError::ConnectionError {}))))
+                } else {
+                    Err(api.error_response(response).await)
+                }
+            })
+            .flat_map(|result| match result {
+                Ok(chunks) => ndjson_stream(chunks).left_stream(),
+                Err(e) => futures::stream::once(async move { Err(e) }).right_stream(),
+            })
+        }
+
         /// Executes a GET request.
         pub async fn get<T: DeserializeOwned + Debug>(
             &self,
@@ -121,22 +250,71 @@ pub mod api_wrapper {
             }
         }
 
+        /// Executes a GET request for a single page of a paginated listing
+        pub async fn get_paginated<T: DeserializeOwned + Debug, P: Into<PageParams>>(
+            &self,
+            endpoint: &str,
+            page_params: P,
+        ) -> Result<Page<T>, ApiError> {
+            let params: PageParams = page_params.into();
+            if let Ok(response) = self.request(endpoint, Method::GET)?.query(&params).send().await {
+                self.extract_response::<PageEnvelope<T>>(response)
+                    .await
+                    .map(|envelope| {
+                        let (items, total) = match envelope {
+                            PageEnvelope::List(items) => (items, None),
+                            PageEnvelope::Posts { posts, total_posts } => (posts, total_posts),
+                        };
+                        Page {
+                            items,
+                            page: params.page,
+                            per_page: params.per_page,
+                            total,
+                        }
+                    })
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
+
+        /// Executes a HEAD request, returning the response headers
+        pub async fn head(&self, endpoint: &str) -> Result<HeaderMap, ApiError> {
+            if let Ok(response) = self.request(endpoint, Method::HEAD)?.send().await {
+                if response.status().is_success() {
+                    Ok(response.headers().clone())
+                } else {
+                    Err(self.error_response(response).await)
+                }
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
+
         /// Executes a DELETE request
         pub async fn delete(
             &self,
             endpoint: &str,
         ) -> Result<(), ApiError> {
             if let Ok(response) = self.request(endpoint, Method::DELETE)?.send().await {
-                match response.error_for_status() {
-                    Ok(_) => Ok(()),
-                    Err(resp) => Err(ApiError::Request {
-                        error: RequestError {
-                            code: resp.status().map_or(0, |s| s.as_u16()),
-                            reason: Some(resp.to_string()),
-                        },
-                    })
-                }
-                
+                self.extract_empty(response).await
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
+
+        /// Executes a DELETE request with a JSON body
+        pub async fn delete_with_body<D: Serialize>(
+            &self,
+            endpoint: &str,
+            data: Option<D>,
+        ) -> Result<(), ApiError> {
+            if let Ok(response) = self
+                .request(endpoint, Method::DELETE)?
+                .json(&data)
+                .send()
+                .await
+            {
+                self.extract_empty(response).await
             } else {
                 Err(ApiError::ConnectionError {})
             }
@@ -159,5 +337,24 @@ pub mod api_wrapper {
                 Err(ApiError::ConnectionError {})
             }
         }
+
+        /// Executes a POST request with a form-encoded (`application/x-www-form-urlencoded`) body, for endpoints that don't accept JSON
+        pub async fn post_form<T: DeserializeOwned + Debug, D: Serialize>(
+            &self,
+            endpoint: &str,
+            data: D,
+        ) -> Result<T, ApiError> {
+            if let Ok(response) = self
+                .request(endpoint, Method::POST)?
+                .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+                .form(&data)
+                .send()
+                .await
+            {
+                self.extract_response::<T>(response).await
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
     }
 }
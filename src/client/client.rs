@@ -1,8 +1,14 @@
 /// This module contains the main [Client] struct, which provides access to all of the other types & methods.
 pub mod api_client {
+    use std::{collections::HashMap, fmt::{self, Debug}, str::FromStr, sync::{Arc, Mutex}};
+
+    use reqwest::{header, Client as ReqwestClient, Proxy, Url};
     use serde_derive::{Deserialize, Serialize};
 
-    use crate::{api_handlers::{CollectionHandler, PostHandler, UserHandler}, api_models, api_wrapper::Api};
+    use crate::{api_handlers::{AdminHandler, CollectionHandler, PostHandler, UserHandler}, api_models::{self, instance::InstanceInfo, responses::NodeInfo}, api_wrapper::Api};
+
+    /// Base URL of the hosted Write.as service
+    pub const WRITEAS_BASE_URL: &str = "https://write.as";
 
     #[derive(Clone, Serialize, Deserialize, Debug)]
     /// The desired authentication method
@@ -30,6 +36,48 @@ pub mod api_client {
         pub reason: Option<String>
     }
 
+    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq, Eq)]
+    /// Describes why the server rejected a request's credentials (see [ApiError::Unauthorized])
+    pub enum AuthFailReason {
+        /// Wrong username, password or token
+        BadCredentials,
+
+        /// The account has been suspended
+        AccountSuspended,
+
+        /// The account's email address hasn't been verified yet
+        EmailNotVerified,
+
+        /// A second authentication factor is required
+        TwoFactorRequired,
+
+        /// Any other authentication failure, with the server's error message
+        Unknown(String)
+    }
+
+    impl AuthFailReason {
+        /// Classifies an authentication failure from an HTTP status code & the server's error message.
+        /// Returns `None` if the response doesn't describe an authentication failure (eg. a 403 for an unrelated permission issue).
+        pub fn from_response(status: u16, message: &str) -> Option<Self> {
+            let lower = message.to_lowercase();
+            if status != 401 && status != 403 {
+                None
+            } else if lower.contains("suspend") {
+                Some(AuthFailReason::AccountSuspended)
+            } else if lower.contains("verif") {
+                Some(AuthFailReason::EmailNotVerified)
+            } else if ["two-factor", "two factor", "2fa", "otp"].iter().any(|k| lower.contains(k)) {
+                Some(AuthFailReason::TwoFactorRequired)
+            } else if status == 403 {
+                None
+            } else if ["password", "credential", "incorrect", "invalid", "user"].iter().any(|k| lower.contains(k)) {
+                Some(AuthFailReason::BadCredentials)
+            } else {
+                Some(AuthFailReason::Unknown(message.to_string()))
+            }
+        }
+    }
+
     #[derive(Clone, Serialize, Deserialize, Debug)]
     #[serde(tag = "type")]
     /// The main Error enum for this library
@@ -40,8 +88,11 @@ pub mod api_client {
             error: RequestError
         },
 
-        /// Raised if authentication fails
-        AuthenticationError{},
+        /// Raised if the server rejects the request's credentials
+        Unauthorized{
+            /// Why authentication failed
+            reason: AuthFailReason
+        },
 
         /// Raised on an unexpected error. Should never appear in normal operation
         UnknownError{},
@@ -52,7 +103,11 @@ pub mod api_client {
         /// Raised if data parsing fails
         ParseError{
             /// Text that serde failed to parse
-            text: String
+            text: String,
+
+            #[serde(default)]
+            /// The serde error message, which usually includes the offending field & position (see [ApiError::field])
+            serde_error: String
         },
 
         /// Raised if connecting to the API server fails
@@ -62,21 +117,330 @@ pub mod api_client {
         LoggedOut{},
 
         /// Raised if invalid data was passed from the user, or if no [Client] instance is defined on the referenced struct
-        UsageError{}
+        UsageError{},
+
+        /// Raised if a model fails client-side validation before being sent
+        ValidationError{
+            /// Descriptions of each failed check
+            errors: Vec<String>
+        },
+
+        /// Raised if reading or writing a local file fails
+        IoError{
+            /// Description of the underlying IO error
+            reason: String
+        },
+
+        /// Raised if one or more steps of a multi-request operation fail
+        BatchError{
+            /// Errors from each failed step, in order
+            errors: Vec<ApiError>
+        }
+    }
+
+    impl ApiError {
+        /// For [ApiError::ParseError], attempts to extract the name of the field that failed to parse from the serde error message
+        /// (eg. `body` from "missing field \`body\` at line 1 column 42")
+        pub fn field(&self) -> Option<&str> {
+            if let ApiError::ParseError { serde_error, .. } = self {
+                let start = serde_error.find("field `")? + "field `".len();
+                let length = serde_error[start..].find('`')?;
+                Some(&serde_error[start..start + length])
+            } else {
+                None
+            }
+        }
+
+        /// Checks if this error was caused by the server returning a 404 status
+        pub fn is_not_found(&self) -> bool {
+            matches!(self, ApiError::Request { error: RequestError { code: 404, .. } })
+        }
+
+        /// Checks whether this error is an HTTP 403 response
+        pub fn is_forbidden(&self) -> bool {
+            matches!(self, ApiError::Request { error: RequestError { code: 403, .. } })
+        }
+    }
+
+
+    fn default_headers() -> header::HeaderMap {
+        let mut headers = header::HeaderMap::new();
+        headers.insert(
+            "Accept",
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            "Content-Type",
+            header::HeaderValue::from_static("application/json"),
+        );
+        headers
+    }
+
+    fn default_http() -> ReqwestClient {
+        ReqwestClient::builder()
+            .default_headers(default_headers())
+            .build()
+            .unwrap_or_default()
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    /// Error returned when parsing an [Auth] from a string fails
+    pub enum AuthParseError {
+        /// The input was empty
+        Empty,
+
+        /// The input was in `username:password` form, but the username was empty
+        EmptyUsername,
+
+        /// The input was in `username:password` form, but the password was empty
+        EmptyPassword
+    }
+
+    impl fmt::Display for AuthParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                AuthParseError::Empty => write!(f, "credentials are empty"),
+                AuthParseError::EmptyUsername => write!(f, "username is empty"),
+                AuthParseError::EmptyPassword => write!(f, "password is empty")
+            }
+        }
+    }
+
+    impl std::error::Error for AuthParseError {}
+
+    impl TryFrom<&str> for Auth {
+        type Error = AuthParseError;
+
+        /// Parses `username:password` as [Auth::Login] (splitting on the first `:`, so passwords may contain colons), or anything else as [Auth::Token]
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(AuthParseError::Empty);
+            }
+
+            match value.split_once(':') {
+                Some(("", _)) => Err(AuthParseError::EmptyUsername),
+                Some((_, "")) => Err(AuthParseError::EmptyPassword),
+                Some((username, password)) => Ok(Auth::Login { username: username.to_string(), password: password.to_string() }),
+                None => Ok(Auth::Token(value.to_string()))
+            }
+        }
+    }
+
+    impl FromStr for Auth {
+        type Err = AuthParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Auth::try_from(s)
+        }
+    }
+
+    /// Returns the [TokenStore] key for an anonymous post's token. Post keys are prefixed with `post:`, so they can't
+    /// collide with the usernames & base URLs used as keys for account tokens.
+    pub fn post_token_key(post_id: &str) -> String {
+        format!("post:{post_id}")
+    }
+
+    /// Persistent storage for API tokens, allowing sessions to be restored without re-authenticating (see [Client::restore_from_store])
+    pub trait TokenStore: Send + Sync + Debug {
+        /// Loads the token stored under `key`, if any
+        fn load(&self, key: &str) -> Result<Option<String>, ApiError>;
+
+        /// Stores a token under `key`, replacing any existing token
+        fn save(&self, key: &str, token: &str) -> Result<(), ApiError>;
+
+        /// Removes the token stored under `key`, if any
+        fn remove(&self, key: &str) -> Result<(), ApiError>;
+    }
+
+    #[derive(Debug, Default)]
+    /// A [TokenStore] that keeps tokens in memory for the lifetime of the process
+    pub struct MemoryTokenStore {
+        tokens: Mutex<HashMap<String, String>>
+    }
+
+    impl MemoryTokenStore {
+        /// Creates an empty store
+        pub fn new() -> Self {
+            MemoryTokenStore::default()
+        }
+    }
+
+    impl TokenStore for MemoryTokenStore {
+        fn load(&self, key: &str) -> Result<Option<String>, ApiError> {
+            Ok(self.tokens.lock().or(Err(ApiError::UnknownError {}))?.get(key).cloned())
+        }
+
+        fn save(&self, key: &str, token: &str) -> Result<(), ApiError> {
+            self.tokens.lock().or(Err(ApiError::UnknownError {}))?.insert(key.to_string(), token.to_string());
+            Ok(())
+        }
+
+        fn remove(&self, key: &str) -> Result<(), ApiError> {
+            self.tokens.lock().or(Err(ApiError::UnknownError {}))?.remove(key);
+            Ok(())
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    /// A set of named, authenticated [Client]s (eg. for tools managing posts for several users)
+    pub struct MultiAccountManager {
+        clients: HashMap<String, Client>
+    }
+
+    impl MultiAccountManager {
+        /// Creates a manager from a map of `name -> client`
+        pub fn new(clients: HashMap<String, Client>) -> Self {
+            MultiAccountManager { clients }
+        }
+
+        /// Authenticates every `(name, auth)` pair concurrently (see [Client::multi_auth])
+        pub async fn authenticate(base_url: String, auths: Vec<(String, Auth)>) -> Result<Self, ApiError> {
+            Client::multi_auth(base_url, auths).await.map(MultiAccountManager::new)
+        }
+
+        /// Retrieves the client for the account named `name`, if any
+        pub fn client(&self, name: &str) -> Option<&Client> {
+            self.clients.get(name)
+        }
+
+        /// Adds (or replaces) the client for the account named `name`
+        pub fn insert(&mut self, name: String, client: Client) -> Option<Client> {
+            self.clients.insert(name, client)
+        }
+
+        /// Iterates over every `(name, client)` pair, in arbitrary order
+        pub fn all_clients(&self) -> impl Iterator<Item = (&str, &Client)> {
+            self.clients.iter().map(|(name, client)| (name.as_str(), client))
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    /// Builder for [Client] instances that need custom transport settings (proxies, etc)
+    pub struct ClientBuilder {
+        base_url: String,
+        proxy: Option<String>,
+        proxy_credentials: Option<(String, String)>,
+        env_proxy: bool,
     }
 
+    impl ClientBuilder {
+        /// Creates a new [ClientBuilder] with a base URL
+        pub fn new(base: String) -> Self {
+            ClientBuilder {
+                base_url: base,
+                proxy: None,
+                proxy_credentials: None,
+                env_proxy: true,
+            }
+        }
+
+        /// Routes all requests through an HTTP/HTTPS proxy
+        pub fn proxy(&mut self, proxy_url: &str) -> &mut Self {
+            self.proxy = Some(proxy_url.to_string());
+            self.proxy_credentials = None;
+            self
+        }
+
+        /// Routes all requests through an HTTP/HTTPS proxy using Basic authentication
+        pub fn proxy_with_credentials(&mut self, proxy_url: &str, username: &str, password: &str) -> &mut Self {
+            self.proxy = Some(proxy_url.to_string());
+            self.proxy_credentials = Some((username.to_string(), password.to_string()));
+            self
+        }
+
+        /// Whether to respect the `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment variables (defaults to `true`)
+        pub fn env_proxy(&mut self, enabled: bool) -> &mut Self {
+            self.env_proxy = enabled;
+            self
+        }
+
+        /// Builds the [Client]. Returns [ApiError::UrlError] if the proxy URL is invalid.
+        pub fn build(&self) -> Result<Client, ApiError> {
+            let mut builder = ReqwestClient::builder().default_headers(default_headers());
+            if !self.env_proxy {
+                builder = builder.no_proxy();
+            }
+
+            if let Some(proxy_url) = self.proxy.clone() {
+                let mut proxy = Proxy::all(proxy_url).or(Err(ApiError::UrlError {}))?;
+                if let Some((username, password)) = self.proxy_credentials.clone() {
+                    proxy = proxy.basic_auth(username.as_str(), password.as_str());
+                }
+                builder = builder.proxy(proxy);
+            }
+
+            match builder.build() {
+                Ok(http) => Ok(Client { _base_url: self.base_url.clone(), _token: None, _base_path: String::new(), _max_post_bytes: None, _http: http, _token_store: None }),
+                Err(_) => Err(ApiError::UnknownError {})
+            }
+        }
+    }
 
     #[derive(Clone, Serialize, Deserialize, Debug)]
     /// Main Client struct
     pub struct Client {
         _base_url: String,
         _token: Option<String>,
+
+        #[serde(default)]
+        _base_path: String,
+
+        #[serde(default)]
+        _max_post_bytes: Option<usize>,
+
+        #[serde(skip, default = "default_http")]
+        _http: ReqwestClient,
+
+        #[serde(skip)]
+        _token_store: Option<Arc<dyn TokenStore>>,
     }
 
     impl Client {
         /// Creates a new client with a base URL
         pub fn new(base: String) -> Self {
-            Client { _base_url: base, _token: None }
+            Client { _base_url: base, _token: None, _base_path: String::new(), _max_post_bytes: None, _http: default_http(), _token_store: None }
+        }
+
+        /// Creates a new client for an instance served under a subpath of `base_url` (eg. `https://example.com` & `/blog`)
+        pub fn new_with_path(base_url: &str, path: &str) -> Result<Self, ApiError> {
+            Url::parse(base_url).or(Err(ApiError::UrlError {}))?;
+            Ok(Client::new(base_url.to_string()).with_base_path(path))
+        }
+
+        /// Sets the subpath the instance is served under. The path is normalized to have a leading `/` & no trailing `/`.
+        pub fn with_base_path(&mut self, path: &str) -> Self {
+            let trimmed = path.trim().trim_matches('/');
+            self._base_path = if trimmed.is_empty() { String::new() } else { format!("/{trimmed}") };
+            self.clone()
+        }
+
+        /// Creates a new client for the hosted Write.as service (see [WRITEAS_BASE_URL])
+        pub fn new_for_writeas() -> Self {
+            Client::new(WRITEAS_BASE_URL.to_string())
+        }
+
+        /// Creates a new client for the hosted Write.as service, and authenticates with an [Auth] enum value
+        pub async fn new_for_writeas_authenticated(auth: Auth) -> Result<Self, ApiError> {
+            Client::new_for_writeas().authenticate(auth).await
+        }
+
+        /// Returns a [ClientBuilder] for configuring a client with custom transport settings
+        #[must_use = "this returns a ClientBuilder, which does nothing until built"]
+        pub fn builder(base: String) -> ClientBuilder {
+            ClientBuilder::new(base)
+        }
+
+        /// Authenticates several accounts on the same instance concurrently, returning a map of `name -> authenticated client`.
+        /// Fails with the first error encountered if any account fails to authenticate.
+        pub async fn multi_auth(base_url: String, auths: Vec<(String, Auth)>) -> Result<HashMap<String, Client>, ApiError> {
+            let client = Client::new(base_url);
+            futures::future::try_join_all(auths.into_iter().map(|(name, auth)| {
+                let client = client.clone();
+                async move { client.try_authenticate(auth).await.map(|c| (name, c)) }
+            }))
+            .await
+            .map(|clients| clients.into_iter().collect())
         }
 
         /// Authenticates with an [Auth] enum value
@@ -98,6 +462,63 @@ pub mod api_client {
             }
         }
 
+        /// Authenticates with an [Auth] enum value, then saves the resulting token to a [TokenStore].
+        /// Tokens from [Auth::Login] are stored under the username; tokens from [Auth::Token] are stored under the base URL.
+        pub async fn authenticate_and_store_token(&mut self, auth: Auth, store: &dyn TokenStore) -> Result<Self, ApiError> {
+            let key = match &auth {
+                Auth::Login { username, .. } => username.clone(),
+                Auth::Token(_) => self.base_url().to_string()
+            };
+            let client = self.authenticate(auth).await?;
+            if let Some(token) = client.token() {
+                store.save(key.as_str(), token.as_str())?;
+            }
+            Ok(client)
+        }
+
+        /// Creates a client from a token previously saved to a [TokenStore] under `key`.
+        /// Returns `None` if no token is stored, or if the server no longer accepts it.
+        pub async fn restore_from_store(base_url: String, store: &dyn TokenStore, key: &str) -> Result<Option<Self>, ApiError> {
+            match store.load(key)? {
+                Some(token) => {
+                    let client = Client::new(base_url).authenticate(Auth::Token(token)).await?;
+                    Ok(client.validate_token().await?.then_some(client))
+                },
+                None => Ok(None)
+            }
+        }
+
+        /// Checks whether the current token is still accepted by the server (via `GET /me`).
+        /// Returns `Ok(false)` if the client has no token or the server rejects it, and propagates any other error.
+        pub async fn validate_token(&self) -> Result<bool, ApiError> {
+            if !self.is_authenticated() {
+                return Ok(false);
+            }
+            match self.api().get::<api_models::users::User>("/me").await {
+                Ok(_) => Ok(true),
+                Err(ApiError::Unauthorized { .. }) | Err(ApiError::Request { error: RequestError { code: 401, .. } }) => Ok(false),
+                Err(e) => Err(e)
+            }
+        }
+
+        /// Authenticates with a token, after checking that the server accepts it (see [Client::validate_token]).
+        /// Fails with [ApiError::Unauthorized] if the token is invalid, leaving `self` unchanged.
+        pub async fn with_token_validated(&mut self, token: String) -> Result<Self, ApiError> {
+            let client = self.try_authenticate(Auth::Token(token)).await?;
+            if client.validate_token().await? {
+                self._token = client._token.clone();
+                Ok(client)
+            } else {
+                Err(ApiError::Unauthorized { reason: AuthFailReason::BadCredentials })
+            }
+        }
+
+        /// Attempts to authenticate with an [Auth] enum value, returning an authenticated copy of this client.
+        /// Unlike [Client::authenticate], `self` is never modified.
+        pub async fn try_authenticate(&self, auth: Auth) -> Result<Self, ApiError> {
+            self.clone().authenticate(auth).await
+        }
+
         /// Deauthenticates from the server
         pub async fn logout(&mut self) -> Result<Self, ApiError> {
             if self.is_authenticated() {
@@ -113,26 +534,128 @@ pub mod api_client {
             }
         }
 
+        /// Invalidates every active session for the authenticated user, not just the current token.
+        /// If the server doesn't support this, falls back to [Client::logout].
+        pub async fn logout_all(&mut self) -> Result<(), ApiError> {
+            if !self.is_authenticated() {
+                return Err(ApiError::LoggedOut {  });
+            }
+
+            match self.api().delete("/auth/all").await {
+                Ok(_) => {
+                    self._token = None;
+                    Ok(())
+                },
+                Err(e) if e.is_not_found() => {
+                    tracing::warn!("Server does not support invalidating all sessions, only the current token will be invalidated");
+                    self.logout().await.map(|_| ())
+                },
+                Err(e) => Err(e)
+            }
+        }
+
         /// Retrieves the base URL
+        #[deprecated(since = "0.2.0", note = "Use base_url() instead")]
         pub fn url(&self) -> String {
             self._base_url.clone()
         }
 
+        /// Retrieves the base URL without allocating
+        pub fn base_url(&self) -> &str {
+            self._base_url.as_str()
+        }
+
+        /// Sets the maximum post body size (in bytes) accepted by the server, used for client-side validation
+        pub fn with_max_post_bytes(&mut self, limit: usize) -> Self {
+            self._max_post_bytes = Some(limit);
+            self.clone()
+        }
+
+        /// Attaches a [TokenStore], used to save the tokens of published anonymous posts & look them up when fetching those posts (see [crate::api_handlers::PostHandler::get_with_fallback])
+        pub fn with_token_store(&mut self, store: Arc<dyn TokenStore>) -> Self {
+            self._token_store = Some(store);
+            self.clone()
+        }
+
+        /// Retrieves the attached [TokenStore], if any
+        pub fn token_store(&self) -> Option<Arc<dyn TokenStore>> {
+            self._token_store.clone()
+        }
+
+        /// Saves a newly published post's token to the attached [TokenStore], if both exist (see [post_token_key])
+        pub(crate) fn store_post_token(&self, post: &api_models::posts::Post) {
+            if let (Some(store), Some(token)) = (self.token_store(), post.token.as_ref()) {
+                if store.save(post_token_key(&post.id).as_str(), token).is_err() {
+                    tracing::warn!("Failed to save the token of post {} to the token store", post.id);
+                }
+            }
+        }
+
+        /// Retrieves the configured maximum post body size, if any
+        pub fn max_post_bytes(&self) -> Option<usize> {
+            self._max_post_bytes
+        }
+
+        /// Retrieves the subpath the instance is served under (empty if served at the root)
+        pub fn base_path(&self) -> &str {
+            self._base_path.as_str()
+        }
+
         /// Retrieves the access token
         pub fn token(&self) -> Option<String> {
             self._token.clone()
         }
 
+        /// Retrieves the shared HTTP client
+        pub fn http(&self) -> ReqwestClient {
+            self._http.clone()
+        }
+
+        /// Returns an unauthenticated copy of this client, sharing its base URL & transport configuration (proxies, etc)
+        pub fn clone_anonymous(&self) -> Client {
+            let mut client = self.clone();
+            client._token = None;
+            client
+        }
+
         /// Checks if the instance is authenticated
         pub fn is_authenticated(&self) -> bool {
             self._token.is_some()
         }
 
+        /// Checks if this client points at the hosted Write.as service (rather than a self-hosted WriteFreely instance)
+        pub fn is_write_as(&self) -> bool {
+            Url::parse(self.base_url())
+                .ok()
+                .and_then(|u| u.host_str().map(|h| h == "write.as" || h.ends_with(".write.as")))
+                .unwrap_or(false)
+        }
+
         /// Returns a new [Api] instance. In general, a new instance should be created for each separate operation to prevent cloned [Client] desync.
         pub fn api(&self) -> Api {
             Api::new(self.clone())
         }
 
+        /// Fetches metadata describing the server instance. Uses a dedicated instance endpoint if available, falling back to NodeInfo.
+        pub async fn instance_info(&self) -> Result<InstanceInfo, ApiError> {
+            match self.api().get::<InstanceInfo>("/instance").await {
+                Err(e) if e.is_not_found() => self.api().get_raw::<NodeInfo>("/nodeinfo").await.map(InstanceInfo::from),
+                result => result
+            }
+        }
+
+        /// Checks whether the server is reachable by sending a HEAD request to the API root. Returns `Ok(true)` on a 2xx
+        /// response, or on the 404 WriteFreely itself answers `HEAD /api` with, and `Ok(false)` if the connection fails.
+        /// Any other error (eg. a 502 from a reverse proxy whose upstream is down) is propagated.
+        pub async fn is_alive(&self) -> Result<bool, ApiError> {
+            match self.api().head("").await {
+                Ok(_) => Ok(true),
+                Err(e) if e.is_not_found() => Ok(true),
+                Err(ApiError::ConnectionError {}) => Ok(false),
+                Err(e) => Err(e)
+            }
+        }
+
         /// Returns a wrapper around User methods
         pub async fn user(&self) -> Result<UserHandler, ApiError> {
             if self.is_authenticated() {
@@ -142,6 +665,17 @@ pub mod api_client {
             }
         }
 
+        /// Returns a wrapper around instance administration methods. Fails with [ApiError::LoggedOut] if unauthenticated.
+        /// Administrator access isn't checked up front (stock WriteFreely doesn't report it in `/me`); the admin
+        /// endpoints themselves reject non-administrators with a 401 or 403 (see [ApiError::is_forbidden]).
+        pub fn admin(&self) -> Result<AdminHandler, ApiError> {
+            if self.is_authenticated() {
+                Ok(AdminHandler::new(self.clone()))
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
         /// Returns a wrapper around Post methods
         pub fn posts(&self) -> PostHandler {
             PostHandler::new(self.clone())
@@ -156,10 +690,10 @@ pub mod api_client {
 
 #[cfg(test)]
 mod tests {
-    use std::{thread::sleep, time::Duration};
+    use std::{io::{Read, Write}, net::TcpListener, sync::Arc, thread::{sleep, spawn, JoinHandle}, time::Duration};
 
     use super::*;
-    use api_client::{Auth, Client};
+    use api_client::{ApiError, Auth, AuthFailReason, AuthParseError, Client, MemoryTokenStore, TokenStore, WRITEAS_BASE_URL};
     use tokio_test;
 
     macro_rules! aw {
@@ -172,15 +706,93 @@ mod tests {
         Client::new("http://0.0.0.0:8080".to_string())
     }
 
+    /// Starts a server that answers a single request with `body` (wrapped in the API's response envelope), returning a
+    /// client pointed at it and a handle resolving to the request line it received
+    fn mock(body: &'static str) -> (Client, JoinHandle<String>) {
+        let (client, handle) = mock_responses(vec![(200, body)]);
+        (client, spawn(move || handle.join().unwrap().remove(0)))
+    }
+
+    /// Starts a server that answers one request per `(status, body)` pair, in order. Successful bodies are wrapped in the
+    /// response envelope, other bodies are sent as the error message. The handle resolves to the request lines received.
+    fn mock_responses(responses: Vec<(u16, &'static str)>) -> (Client, JoinHandle<Vec<String>>) {
+        let (client, handle) = mock_requests(responses);
+        (client, spawn(move || handle.join().unwrap().iter().map(|r| r.lines().next().unwrap_or_default().to_string()).collect()))
+    }
+
+    /// Like [mock_responses], but the handle resolves to the full requests received (headers & body)
+    fn mock_requests(responses: Vec<(u16, &'static str)>) -> (Client, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let handle = spawn(move || {
+            responses
+                .into_iter()
+                .map(|(status, body)| {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut request = Vec::new();
+                    let mut buffer = [0u8; 8192];
+                    loop {
+                        let length = stream.read(&mut buffer).unwrap();
+                        request.extend_from_slice(&buffer[..length]);
+                        let text = String::from_utf8_lossy(&request).to_string();
+                        if let Some((head, content)) = text.split_once("\r\n\r\n") {
+                            let expected = head
+                                .lines()
+                                .find_map(|l| l.to_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap_or(0)))
+                                .unwrap_or(0);
+                            if content.len() >= expected || length == 0 {
+                                break;
+                            }
+                        } else if length == 0 {
+                            break;
+                        }
+                    }
+                    let body = if status < 300 {
+                        format!("{{\"code\": {status}, \"data\": {body}}}")
+                    } else {
+                        format!("{{\"code\": {status}, \"error_msg\": \"{body}\"}}")
+                    };
+                    // Respond the way a camelCase server would
+                    let body = if cfg!(feature = "camel-case-api") { body.replace("\"error_msg\"", "\"errorMsg\"") } else { body };
+                    let response = format!(
+                        "HTTP/1.1 {status} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    );
+                    stream.write_all(response.as_bytes()).unwrap();
+                    String::from_utf8_lossy(&request).to_string()
+                })
+                .collect()
+        });
+        (Client::new(format!("http://{address}")), handle)
+    }
+
     async fn auth() -> Client {
         Client::new("http://0.0.0.0:8080".to_string()).authenticate(Auth::Login { username: "username".to_string(), password: "password".to_string() }).await.unwrap()
     }
 
     #[test]
+    #[allow(deprecated)]
     fn eq_url() {
         assert_eq!(anon().url(), "http://0.0.0.0:8080".to_string());
     }
 
+    #[test]
+    fn eq_base_url() {
+        assert_eq!(anon().base_url(), "http://0.0.0.0:8080");
+        assert_eq!(anon().api().base_url(), "http://0.0.0.0:8080");
+    }
+
+    #[test]
+    fn builder_proxy() {
+        assert!(Client::builder("http://0.0.0.0:8080".to_string()).proxy("http://127.0.0.1:3128").build().is_ok());
+        assert!(Client::builder("http://0.0.0.0:8080".to_string()).proxy_with_credentials("http://127.0.0.1:3128", "user", "pass").env_proxy(false).build().is_ok());
+    }
+
+    #[test]
+    fn builder_bad_proxy() {
+        assert!(Client::builder("http://0.0.0.0:8080".to_string()).proxy("not a url").build().is_err());
+    }
+
     #[test]
     fn anon_no_token() {
         assert!(!anon().is_authenticated());
@@ -196,6 +808,28 @@ mod tests {
         assert!(aw!(anon().authenticate(Auth::Login { username: "usernameee".to_string(), password: "passwordeee".to_string() })).is_err())
     }
 
+    #[test]
+    fn auth_bad_login_unchanged() {
+        let mut client = anon();
+        assert!(aw!(client.authenticate(Auth::Login { username: "usernameee".to_string(), password: "passwordeee".to_string() })).is_err());
+        assert!(!client.is_authenticated());
+    }
+
+    #[test]
+    fn try_auth_bad_login() {
+        let client = anon();
+        assert!(aw!(client.try_authenticate(Auth::Login { username: "usernameee".to_string(), password: "passwordeee".to_string() })).is_err());
+        assert!(!client.is_authenticated());
+    }
+
+    #[test]
+    fn try_auth_token() {
+        let client = anon();
+        let authed = aw!(client.try_authenticate(Auth::Token("token".to_string()))).unwrap();
+        assert!(authed.is_authenticated());
+        assert!(!client.is_authenticated());
+    }
+
     #[test]
     fn auth_logout() {
         let mut authed = aw!(auth());
@@ -206,4 +840,492 @@ mod tests {
         assert!(!logged_out.unwrap().is_authenticated());
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn auth_fail_reasons() {
+        assert_eq!(AuthFailReason::from_response(401, "Incorrect password."), Some(AuthFailReason::BadCredentials));
+        assert_eq!(AuthFailReason::from_response(403, "Account is suspended."), Some(AuthFailReason::AccountSuspended));
+        assert_eq!(AuthFailReason::from_response(403, "Please verify your email address."), Some(AuthFailReason::EmailNotVerified));
+        assert_eq!(AuthFailReason::from_response(401, "Two-factor code required."), Some(AuthFailReason::TwoFactorRequired));
+        assert_eq!(AuthFailReason::from_response(401, "Nope."), Some(AuthFailReason::Unknown("Nope.".to_string())));
+        assert_eq!(AuthFailReason::from_response(403, "You don't have permission to update this post."), None);
+        assert_eq!(AuthFailReason::from_response(404, "Incorrect password."), None);
+    }
+
+    #[test]
+    fn base_path_url() {
+        let client = Client::new_with_path("https://example.com", "/blog").unwrap();
+        assert_eq!(client.api().url("/posts").unwrap().as_str(), "https://example.com/blog/api/posts");
+        assert_eq!(Client::new_with_path("https://example.com", "blog/").unwrap().base_path(), "/blog");
+        assert_eq!(anon().api().url("/posts").unwrap().as_str(), "http://0.0.0.0:8080/api/posts");
+        assert!(Client::new_with_path("not a url", "/blog").is_err());
+    }
+
+    #[test]
+    fn is_alive_reachability() {
+        assert!(!aw!(Client::new("http://127.0.0.1:9".to_string()).is_alive()).unwrap());
+
+        let (client, request) = mock_responses(vec![(404, "Not found")]);
+        assert!(aw!(client.is_alive()).unwrap());
+        assert_eq!(request.join().unwrap(), ["HEAD /api HTTP/1.1"]);
+
+        let (client, _) = mock_responses(vec![(500, "Bad gateway")]);
+        assert!(matches!(aw!(client.is_alive()), Err(ApiError::Request { .. })));
+    }
+
+    #[test]
+    fn unauthorized_without_body() {
+        let (client, _) = mock_responses(vec![
+            (401, "Invalid access token"),
+            (401, "Invalid access token"),
+            (401, "Invalid access token"),
+            (403, "Not your post"),
+        ]);
+        let unauthorized = |e: ApiError| matches!(e, ApiError::Unauthorized { reason: AuthFailReason::BadCredentials });
+        assert!(unauthorized(aw!(client.api().delete("/collections/blog")).unwrap_err()));
+        // HEAD responses carry no message, so only the status is known
+        assert!(matches!(aw!(client.api().head("/me")), Err(ApiError::Unauthorized { .. })));
+        assert!(unauthorized(aw!(client.api().get_raw::<serde_json::Value>("/me")).unwrap_err()));
+        assert!(aw!(client.api().delete("/posts/abc")).unwrap_err().is_forbidden());
+    }
+
+    #[test]
+    fn writeas_client() {
+        assert_eq!(Client::new_for_writeas().base_url(), WRITEAS_BASE_URL);
+        assert_eq!(Client::new_for_writeas().api().url("/me").unwrap().as_str(), "https://write.as/api/me");
+    }
+
+    #[test]
+    fn is_write_as() {
+        assert!(Client::new_for_writeas().is_write_as());
+        assert!(!anon().is_write_as());
+        assert!(!Client::new("https://notwrite.as".to_string()).is_write_as());
+    }
+
+    #[test]
+    fn ndjson_stream_lines() {
+        use futures::StreamExt;
+
+        let chunks: Vec<Result<&[u8], ApiError>> = vec![
+            Ok(b"{\"a\": 1}\n{\"a\""),
+            Ok(b": 2}\n\nnot json\n"),
+            Ok(b"{\"a\": 3}"),
+        ];
+        let values: Vec<Result<serde_json::Value, ApiError>> =
+            aw!(crate::api_wrapper::ndjson_stream(futures::stream::iter(chunks)).collect());
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0].as_ref().unwrap()["a"], 1);
+        assert_eq!(values[1].as_ref().unwrap()["a"], 2);
+        assert!(matches!(&values[2], Err(ApiError::ParseError { text, .. }) if text == "line 4: not json"));
+        assert_eq!(values[3].as_ref().unwrap()["a"], 3);
+    }
+
+    #[test]
+    fn token_store() {
+        let store = MemoryTokenStore::new();
+        let client = aw!(anon().authenticate_and_store_token(Auth::Token("token".to_string()), &store)).unwrap();
+        assert!(client.is_authenticated());
+        assert_eq!(store.load("http://0.0.0.0:8080").unwrap().as_deref(), Some("token"));
+
+        store.remove("http://0.0.0.0:8080").unwrap();
+        assert!(store.load("http://0.0.0.0:8080").unwrap().is_none());
+        assert!(aw!(Client::restore_from_store("http://0.0.0.0:8080".to_string(), &store, "missing")).unwrap().is_none());
+    }
+
+    const POSTS: &str = r#"[{"id": "abc", "slug": null, "appearance": "norm", "language": "en", "rtl": false, "created": "2024-01-01T00:00:00Z", "title": "Hello", "body": "Hello world", "tags": ["rust"], "views": 1, "collection": null, "token": null}]"#;
+
+    const COLLECTION: &str = r#"{"alias": "blog", "title": "Blog", "description": null, "style_sheet": null, "public": true, "views": null, "verification_link": null, "total_posts": null}"#;
+
+    fn mock_collection(mut responses: Vec<(u16, &'static str)>) -> (crate::api_models::collections::Collection, JoinHandle<Vec<String>>) {
+        responses.insert(0, (200, COLLECTION));
+        let (client, requests) = mock_responses(responses);
+        (aw!(client.collections().get("blog")).unwrap(), requests)
+    }
+
+    fn post_list(ids: std::ops::Range<usize>) -> &'static str {
+        let posts: Vec<String> = ids.map(|i| format!(r#"{{"id": "p{i}", "body": "", "appearance": "norm", "rtl": false, "tags": []}}"#)).collect();
+        Box::leak(format!("[{}]", posts.join(", ")).into_boxed_str())
+    }
+
+    fn collection_page(ids: std::ops::Range<usize>, total: usize) -> &'static str {
+        let key = if cfg!(feature = "camel-case-api") { "totalPosts" } else { "total_posts" };
+        Box::leak(format!(r#"{{"posts": {}, "{key}": {total}}}"#, post_list(ids)).into_boxed_str())
+    }
+
+    #[test]
+    fn recent_posts() {
+        let (client, request) = mock(POSTS);
+        let posts = aw!(client.posts().recent(5)).unwrap();
+        assert_eq!(request.join().unwrap(), "GET /api/posts?limit=5 HTTP/1.1");
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].client.as_ref().unwrap().base_url(), client.base_url());
+
+        let (client, request) = mock(POSTS);
+        aw!(client.posts().recent_by_tag("#rust", 10)).unwrap();
+        assert_eq!(request.join().unwrap(), "GET /api/posts?limit=10&tag=rust HTTP/1.1");
+    }
+
+    #[test]
+    fn parse_error_field() {
+        let serde_error = serde_json::from_str::<crate::api_models::requests::PostToken>("{}").unwrap_err().to_string();
+        let error = ApiError::ParseError { text: "{}".to_string(), serde_error };
+        assert_eq!(error.field(), Some("token"));
+        assert_eq!(ApiError::ParseError { text: String::new(), serde_error: "EOF while parsing".to_string() }.field(), None);
+        assert_eq!(ApiError::UsageError {}.field(), None);
+    }
+
+    #[test]
+    fn auth_from_str() {
+        assert!(matches!(Auth::try_from("mytoken"), Ok(Auth::Token(t)) if t == "mytoken"));
+        assert!(matches!(
+            "user:pa:ss".parse::<Auth>(),
+            Ok(Auth::Login { username, password }) if username == "user" && password == "pa:ss"
+        ));
+        assert_eq!(Auth::try_from("  ").unwrap_err(), AuthParseError::Empty);
+        assert_eq!(Auth::try_from(":pass").unwrap_err(), AuthParseError::EmptyUsername);
+        assert_eq!(Auth::try_from("user:").unwrap_err(), AuthParseError::EmptyPassword);
+    }
+
+    #[test]
+    fn clone_anonymous() {
+        let client = aw!(anon().with_base_path("/blog").authenticate(Auth::Token("token".to_string()))).unwrap();
+        let anonymous = client.clone_anonymous();
+        assert!(client.is_authenticated());
+        assert!(!anonymous.is_authenticated());
+        assert_eq!(anonymous.base_url(), client.base_url());
+        assert_eq!(anonymous.base_path(), "/blog");
+    }
+
+    #[test]
+    fn get_with_fallback() {
+        let (client, requests) = mock_responses(vec![(403, "forbidden")]);
+        assert!(aw!(client.posts().get_with_fallback("abc")).unwrap_err().is_forbidden());
+        assert_eq!(requests.join().unwrap(), vec!["GET /api/posts/abc HTTP/1.1"]);
+
+        let store = Arc::new(MemoryTokenStore::new());
+        let (mut client, requests) = mock_responses(vec![(403, "forbidden")]);
+        let client = client.with_token_store(store.clone());
+        assert!(aw!(client.posts().get_with_fallback("abc")).unwrap_err().is_forbidden());
+        assert_eq!(requests.join().unwrap().len(), 1);
+
+        let published = r#"{"id": "abc", "body": "Hi", "appearance": "norm", "rtl": false, "tags": [], "token": "secret"}"#;
+        let (mut client, fallback) = mock_responses(vec![(201, published), (403, "forbidden"), (200, &POSTS[1..POSTS.len() - 1])]);
+        let client = client.with_token_store(store.clone());
+        aw!(client.posts().create("Hi".to_string()).build().unwrap().publish()).unwrap();
+        assert_eq!(store.load("post:abc").unwrap().as_deref(), Some("secret"));
+        assert!(store.load("abc").unwrap().is_none());
+        let post = aw!(client.posts().get_with_fallback("abc")).unwrap();
+        assert_eq!(post.token.as_deref(), Some("secret"));
+        assert_eq!(fallback.join().unwrap()[2], "GET /api/posts/abc?token=secret HTTP/1.1");
+    }
+
+    #[test]
+    fn move_pinned_post() {
+        let (collection, requests) = mock_collection(vec![(200, r#"[{"code": 200, "id": "abc"}]"#), (200, r#"[{"code": 200, "id": "abc"}]"#)]);
+        let result = aw!(collection.move_pinned_post("abc", 3)).unwrap();
+        assert!(matches!(result, crate::api_models::collections::PinResult::Success { code: 200, .. }));
+        assert_eq!(
+            requests.join().unwrap()[1..],
+            ["POST /api/collections/blog/unpin HTTP/1.1", "POST /api/collections/blog/pin HTTP/1.1"]
+        );
+
+        let (collection, requests) = mock_collection(vec![(200, r#"[{"code": 404, "error_msg": "not pinned"}]"#)]);
+        assert!(aw!(collection.move_pinned_post("abc", 3)).unwrap_err().is_not_found());
+        assert_eq!(requests.join().unwrap().len(), 2);
+
+        let (collection, _) = mock_collection(vec![(200, r#"[{"code": 200, "id": "abc"}]"#), (200, r#"[{"code": 500, "error_msg": "oops"}]"#)]);
+        assert!(matches!(aw!(collection.move_pinned_post("abc", 3)), Err(ApiError::Request { .. })));
+    }
+
+    #[test]
+    fn swap_pinned_posts_restores_on_failure() {
+        let posts = if cfg!(feature = "camel-case-api") {
+            r#"[{"id": "a", "body": "", "appearance": "norm", "rtl": false, "tags": [], "pinnedPosition": 1}, {"id": "b", "body": "", "appearance": "norm", "rtl": false, "tags": [], "pinnedPosition": 2}]"#
+        } else {
+            r#"[{"id": "a", "body": "", "appearance": "norm", "rtl": false, "tags": [], "pinned_position": 1}, {"id": "b", "body": "", "appearance": "norm", "rtl": false, "tags": [], "pinned_position": 2}]"#
+        };
+        let unpinned = r#"[{"code": 200, "id": "a"}, {"code": 200, "id": "b"}]"#;
+        let (collection, requests) = mock_collection(vec![
+            (200, posts),
+            (200, unpinned),
+            (200, r#"[{"code": 200, "id": "a"}, {"code": 500, "error_msg": "oops"}]"#),
+            (200, unpinned),
+            (200, unpinned),
+        ]);
+        assert!(matches!(aw!(collection.swap_pinned_posts("a", "b")), Err(ApiError::Request { .. })));
+        assert_eq!(requests.join().unwrap()[5], "POST /api/collections/blog/pin HTTP/1.1");
+    }
+
+    #[test]
+    fn admin_requires_authentication() {
+        assert!(matches!(anon().admin(), Err(ApiError::LoggedOut {})));
+
+        let stats = if cfg!(feature = "camel-case-api") {
+            r#"{"totalUsers": 2, "totalPosts": 10, "totalCollections": 3}"#
+        } else {
+            r#"{"total_users": 2, "total_posts": 10, "total_collections": 3}"#
+        };
+        let (mut client, requests) = mock_responses(vec![(403, "Forbidden"), (200, stats)]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        assert!(aw!(client.admin().unwrap().instance_stats()).unwrap_err().is_forbidden());
+        let stats = aw!(client.admin().unwrap().instance_stats()).unwrap();
+        assert_eq!((stats.total_users, stats.total_posts, stats.suspended_users), (2, 10, None));
+        assert_eq!(requests.join().unwrap(), vec!["GET /api/admin/stats HTTP/1.1"; 2]);
+    }
+
+    #[test]
+    fn multi_auth() {
+        use api_client::MultiAccountManager;
+
+        let manager = aw!(MultiAccountManager::authenticate(
+            "http://0.0.0.0:8080".to_string(),
+            vec![("alice".to_string(), Auth::Token("a".to_string())), ("bob".to_string(), Auth::Token("b".to_string()))]
+        ))
+        .unwrap();
+        assert_eq!(manager.client("alice").unwrap().token().as_deref(), Some("a"));
+        assert_eq!(manager.client("bob").unwrap().token().as_deref(), Some("b"));
+        assert!(manager.client("carol").is_none());
+
+        let mut names: Vec<&str> = manager.all_clients().map(|(name, _)| name).collect();
+        names.sort();
+        assert_eq!(names, vec!["alice", "bob"]);
+    }
+
+    #[test]
+    fn collection_stream() {
+        use futures::StreamExt;
+
+        let (mut collection, requests) = mock_collection(vec![(200, POSTS), (200, "[]")]);
+        assert_eq!(aw!(collection.next()).unwrap().unwrap().id, "abc");
+        assert!(aw!(collection.next()).is_none());
+        assert_eq!(requests.join().unwrap()[1], "GET /api/collections/blog/posts?page=1&limit=25 HTTP/1.1");
+
+        let mut detached = collection.clone();
+        detached.client = None;
+        assert!(matches!(aw!(detached.next()), Some(Err(ApiError::UsageError {}))));
+        assert!(aw!(detached.next()).is_none());
+    }
+
+    #[test]
+    fn validate_token() {
+        assert!(!aw!(anon().validate_token()).unwrap());
+
+        let (mut client, requests) = mock_responses(vec![
+            (401, "Invalid access token."),
+            (200, r#"{"username": "user", "email": null, "created": null}"#),
+        ]);
+        assert!(matches!(
+            aw!(client.with_token_validated("expired".to_string())),
+            Err(ApiError::Unauthorized { reason: AuthFailReason::BadCredentials })
+        ));
+        assert!(!client.is_authenticated());
+
+        let client = aw!(client.with_token_validated("valid".to_string())).unwrap();
+        assert_eq!(client.token().as_deref(), Some("valid"));
+        assert_eq!(requests.join().unwrap(), vec!["GET /api/me HTTP/1.1"; 2]);
+    }
+
+    #[test]
+    fn delete_with_posts() {
+        let (collection, requests) = mock_collection(vec![(200, collection_page(0..2, 2)), (500, "oops"), (200, "null"), (400, "Collection not empty")]);
+        match aw!(collection.delete_or_clear(true)) {
+            Err(ApiError::BatchError { errors }) => assert_eq!(errors.len(), 2),
+            other => panic!("unexpected result: {other:?}"),
+        }
+        assert_eq!(
+            requests.join().unwrap()[2..],
+            ["DELETE /api/posts/p0 HTTP/1.1", "DELETE /api/posts/p1 HTTP/1.1", "DELETE /api/collections/blog HTTP/1.1"]
+        );
+
+        let (collection, requests) = mock_collection(vec![(200, collection_page(0..10, 11)), (200, collection_page(10..11, 11))]
+            .into_iter()
+            .chain(vec![(200, "null"); 12])
+            .collect());
+        aw!(collection.delete_with_posts()).unwrap();
+        let requests = requests.join().unwrap();
+        assert_eq!(requests.len(), 15);
+        assert_eq!(requests[14], "DELETE /api/collections/blog HTTP/1.1");
+    }
+
+    #[test]
+    fn post_form() {
+        let (client, request) = mock(r#"{"access_token": "abc"}"#);
+        let token: serde_json::Value = aw!(client
+            .api()
+            .post_form("/oauth/token", [("grant_type", "password"), ("username", "user")]))
+        .unwrap();
+        assert_eq!(token["access_token"], "abc");
+        assert_eq!(request.join().unwrap(), "POST /api/oauth/token HTTP/1.1");
+    }
+
+    #[test]
+    fn following() {
+        let (mut client, requests) = mock_responses(vec![
+            (200, r#"{"username": "user", "email": null, "created": null}"#),
+            (200, POSTS),
+            (200, r#"[{"url": "https://example.social/blog/", "title": "Remote"}]"#),
+            (200, "null"),
+        ]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let user = aw!(client.user()).unwrap();
+        let posts = aw!(user.following_posts(2, 10)).unwrap();
+        assert_eq!(posts[0].client.as_ref().unwrap().base_url(), client.base_url());
+        let following = aw!(user.following()).unwrap();
+        assert_eq!((following[0].url.as_str(), following[0].title.as_deref()), ("https://example.social/blog/", Some("Remote")));
+        aw!(user.follow("https://example.social/blog/")).unwrap();
+        assert_eq!(
+            requests.join().unwrap()[1..],
+            [
+                "GET /api/me/following/posts?page=2&limit=10 HTTP/1.1",
+                "GET /api/me/following HTTP/1.1",
+                "POST /api/me/following HTTP/1.1"
+            ]
+        );
+    }
+
+    #[test]
+    fn stream_posts_server_page_size() {
+        use futures::StreamExt;
+
+        let (collection, requests) = mock_collection(vec![(200, post_list(0..10)), (200, post_list(10..20)), (200, post_list(20..22))]);
+        let posts: Vec<crate::api_models::posts::Post> = aw!(collection.stream_posts(25).map(|p| p.unwrap()).collect());
+        assert_eq!(posts.len(), 22);
+        assert_eq!(posts[21].id, "p21");
+        assert_eq!(requests.join().unwrap()[3], "GET /api/collections/blog/posts?page=3&limit=25 HTTP/1.1");
+    }
+
+    #[test]
+    fn stream_posts_repeated_pages() {
+        use futures::StreamExt;
+
+        let (collection, requests) = mock_collection(vec![(200, post_list(0..30)), (200, post_list(0..30))]);
+        let posts: Vec<crate::api_models::posts::Post> = aw!(collection.stream_posts(25).map(|p| p.unwrap()).collect());
+        assert_eq!(posts.len(), 30);
+        assert_eq!(requests.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn user_posts_page() {
+        use futures::StreamExt;
+
+        let (mut client, requests) = mock_responses(vec![
+            (200, r#"{"username": "user", "email": null, "created": null}"#),
+            (200, post_list(0..25)),
+            (200, post_list(0..25)),
+            (200, post_list(0..25)),
+        ]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let user = aw!(client.user()).unwrap();
+        let page = aw!(user.posts_page(2, 10)).unwrap();
+        assert_eq!((page.items.first().unwrap().id.as_str(), page.items.len(), page.total), ("p10", 10, Some(25)));
+        let posts: Vec<crate::api_models::posts::Post> = aw!(user.stream_posts(20).map(|p| p.unwrap()).collect());
+        assert_eq!(posts.len(), 25);
+        assert_eq!(requests.join().unwrap()[1..], vec!["GET /api/me/posts HTTP/1.1"; 3]);
+    }
+
+    #[test]
+    fn find_user_posts() {
+        let (mut client, requests) = mock_responses(vec![(200, post_list(0..30))]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let post = aw!(client.posts().find(|p| p.id == "p27")).unwrap();
+        assert_eq!(post.map(|p| p.id).as_deref(), Some("p27"));
+        assert_eq!(requests.join().unwrap(), ["GET /api/me/posts HTTP/1.1"]);
+    }
+
+    #[test]
+    fn delete_older_than() {
+        let posts = r#"[{"id": "old", "body": "", "appearance": "norm", "rtl": false, "tags": [], "created": "2000-01-01T00:00:00Z"}, {"id": "new", "body": "", "appearance": "norm", "rtl": false, "tags": [], "created": "2999-01-01T00:00:00Z"}]"#;
+        let (mut client, requests) = mock_responses(vec![(200, posts), (200, "null")]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let (deleted, errors) = aw!(client.posts().delete_older_than(Duration::from_secs(30 * 24 * 60 * 60))).unwrap();
+        assert_eq!(deleted, 1);
+        assert!(errors.is_empty());
+        assert_eq!(requests.join().unwrap(), ["GET /api/me/posts HTTP/1.1", "DELETE /api/posts/old HTTP/1.1"]);
+
+        let posts = r#"[{"id": "a", "body": "", "appearance": "norm", "rtl": false, "tags": [], "created": "2000-01-01T00:00:00Z"}, {"id": "b", "body": "", "appearance": "norm", "rtl": false, "tags": [], "created": "2000-01-01T00:00:00Z"}, {"id": "c", "body": "", "appearance": "norm", "rtl": false, "tags": [], "created": "2000-01-01T00:00:00Z"}]"#;
+        let (mut client, requests) = mock_responses(vec![(200, posts), (500, "oops"), (200, "null"), (200, "null")]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let (deleted, errors) = aw!(client.posts().delete_older_than(Duration::from_secs(60))).unwrap();
+        assert_eq!(deleted, 2);
+        assert!(matches!(&errors[..], [ApiError::Request { error }] if error.code == 500));
+        assert_eq!(requests.join().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn user_view_counts() {
+        let posts = r#"[{"id": "a", "body": "", "appearance": "norm", "rtl": false, "tags": [], "views": 3}, {"id": "b", "body": "", "appearance": "norm", "rtl": false, "tags": [], "views": 7}]"#;
+        let (mut client, requests) = mock_responses(vec![(200, r#"{"username": "user", "email": null, "created": null}"#), (200, posts), (200, posts)]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let user = aw!(client.user()).unwrap();
+        assert_eq!(aw!(user.most_viewed_posts(1)).unwrap()[0].id, "b");
+        assert_eq!(aw!(user.total_views()).unwrap(), 10);
+        assert_eq!(requests.join().unwrap()[1..], vec!["GET /api/me/posts HTTP/1.1"; 2]);
+    }
+
+    #[test]
+    fn find_post_beyond_first_page() {
+        let (collection, requests) = mock_collection(vec![(200, collection_page(0..10, 12)), (200, collection_page(10..12, 12))]);
+        let post = aw!(collection.find_post(|p| p.id == "p11")).unwrap();
+        assert_eq!(post.map(|p| p.id).as_deref(), Some("p11"));
+        assert_eq!(requests.join().unwrap()[2], "GET /api/collections/blog/posts?page=2&limit=25 HTTP/1.1");
+    }
+
+    #[test]
+    fn export_web_route() {
+        let (mut client, requests) = mock_responses(vec![(200, r#"{"username": "user", "email": null, "created": null}"#), (200, "[]")]);
+        let client = aw!(client.authenticate(Auth::Token("token".to_string()))).unwrap();
+        let user = aw!(client.user()).unwrap();
+        assert!(!aw!(user.export(crate::api_models::users::ExportFormat::Json)).unwrap().is_empty());
+        assert_eq!(requests.join().unwrap()[1], "GET /me/export.json HTTP/1.1");
+    }
+
+    #[test]
+    fn reorder_pinned_posts_restores_on_failure() {
+        let posts = if cfg!(feature = "camel-case-api") {
+            r#"[{"id": "a", "body": "", "appearance": "norm", "rtl": false, "tags": [], "pinnedPosition": 1}]"#
+        } else {
+            r#"[{"id": "a", "body": "", "appearance": "norm", "rtl": false, "tags": [], "pinned_position": 1}]"#
+        };
+        let (collection, requests) = mock_collection(vec![
+            (200, posts),
+            (200, r#"[{"code": 200, "id": "a"}]"#),
+            (200, r#"[{"code": 404, "error_msg": "not found"}]"#),
+            (200, r#"[{"code": 200, "id": "b"}]"#),
+            (200, r#"[{"code": 200, "id": "a"}]"#),
+        ]);
+        assert!(aw!(collection.reorder_pinned_posts(&["b"])).unwrap_err().is_not_found());
+        assert_eq!(
+            requests.join().unwrap()[3..],
+            ["POST /api/collections/blog/pin HTTP/1.1", "POST /api/collections/blog/unpin HTTP/1.1", "POST /api/collections/blog/pin HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn collection_update_no_op() {
+        let (collection, requests) = mock_collection(vec![(200, COLLECTION)]);
+        assert_eq!(aw!(collection.update(collection.build_update().build().unwrap())).unwrap().alias, "blog");
+        assert_eq!(aw!(collection.build_update().build().unwrap().update()).unwrap().alias, "blog");
+        assert_eq!(requests.join().unwrap(), vec!["GET /api/collections/blog HTTP/1.1"; 2]);
+    }
+
+    #[test]
+    fn collection_is_empty() {
+        let (mut collection, requests) = mock_collection(vec![(200, "[]"), (200, POSTS)]);
+        collection.total_posts = Some(3);
+        assert!(aw!(collection.is_empty()).unwrap());
+        collection.total_posts = Some(0);
+        assert!(!aw!(collection.is_empty()).unwrap());
+        assert_eq!(requests.join().unwrap()[1..], vec!["GET /api/collections/blog/posts?page=1&limit=1 HTTP/1.1"; 2]);
+    }
+
+    #[test]
+    fn visibility_setters_keep_mathjax() {
+        let (client, requests) = mock_requests(vec![(200, COLLECTION), (200, COLLECTION)]);
+        let collection = aw!(client.collections().get("blog")).unwrap();
+        aw!(collection.make_public()).unwrap();
+        let request = requests.join().unwrap().remove(1);
+        assert!(request.starts_with("POST /api/collections/blog HTTP/1.1"));
+        assert!(request.contains("\"visibility\""));
+        assert!(!request.contains("mathjax"));
+    }
+}
@@ -1,14 +1,22 @@
 /// This module provides wrappers for top-level (ie, not referencing a specific entity) API methods
 pub mod api_handlers {
 
+    use std::{path::Path, time::Duration};
+
+    use futures::StreamExt;
+    use reqwest::Method;
     use serde_derive::{Deserialize, Serialize};
 
     use crate::{
-        api_client::{ApiError, Client},
+        api_client::{post_token_key, ApiError, Client},
         api_models::{
-            collections::Collection,
-            posts::{Post, PostCreation, PostCreationBuilder},
-            users::User,
+            channels::Channel,
+            collections::{Collection, FollowedCollection},
+            diff::{diff_post_lists, PostDiff},
+            instance::InstanceStats,
+            pagination::{Page, Paginator},
+            posts::{utils::most_viewed, Post, PostCreation, PostCreationBuilder, PostTemplate},
+            users::{ExportFormat, User},
         },
     };
 
@@ -43,7 +51,18 @@ pub mod api_handlers {
             self.current.clone()
         }
 
-        /// Returns all [Post]s associated with the authenticated [User]
+        /// Returns whether the authenticated [User] has a Write.as Pro subscription, if user info is available
+        pub fn is_pro(&self) -> Option<bool> {
+            self.current.as_ref().map(|u| u.is_pro)
+        }
+
+        /// Checks whether the authenticated [User] can upload images (requires Write.as Pro)
+        pub fn can_upload_images(&self) -> bool {
+            self.is_pro().unwrap_or(false) && self.client.is_write_as()
+        }
+
+        /// Returns all [Post]s associated with the authenticated [User].
+        /// This fetches every post in a single request, and may be slow for large accounts (the server doesn't paginate this listing).
         pub async fn posts(&self) -> Result<Vec<Post>, ApiError> {
             if self.client.is_authenticated() {
                 self.client
@@ -60,6 +79,95 @@ pub mod api_handlers {
             }
         }
 
+        /// Fetches every [Post] of the authenticated [User], returning the `limit` most viewed (the API can't sort by views)
+        pub async fn most_viewed_posts(&self, limit: u64) -> Result<Vec<Post>, ApiError> {
+            self.posts().await.map(|posts| most_viewed(posts, limit))
+        }
+
+        /// Sums the view counts of every [Post] of the authenticated [User]
+        pub async fn total_views(&self) -> Result<u64, ApiError> {
+            self.posts().await.map(|posts| posts.iter().map(|p| p.views.unwrap_or(0)).sum())
+        }
+
+        /// Returns a single page of [Post]s from the collections the authenticated [User] follows (including remote collections)
+        pub async fn following_posts(&self, page: u64, per_page: u64) -> Result<Vec<Post>, ApiError> {
+            if self.client.is_authenticated() {
+                self.client
+                    .api()
+                    .get_paginated::<Post, _>("/me/following/posts", (page, per_page))
+                    .await
+                    .map(|v| v.items.into_iter().map(|mut x| x.with_client(self.client.clone())).collect())
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Returns every collection the authenticated [User] follows
+        pub async fn following(&self) -> Result<Vec<FollowedCollection>, ApiError> {
+            if self.client.is_authenticated() {
+                self.client.api().get::<Vec<FollowedCollection>>("/me/following").await
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Follows a (possibly remote) collection by its public URL
+        pub async fn follow(&self, collection_url: &str) -> Result<(), ApiError> {
+            if self.client.is_authenticated() {
+                let params = FollowParameters { url: collection_url.to_string() };
+                self.client
+                    .api()
+                    .post::<serde_json::Value, FollowParameters>("/me/following", Some(params))
+                    .await
+                    .map(|_| ())
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Unfollows a collection by its public URL
+        pub async fn unfollow(&self, collection_url: &str) -> Result<(), ApiError> {
+            if self.client.is_authenticated() {
+                let params = FollowParameters { url: collection_url.to_string() };
+                self.client.api().delete_with_body("/me/following", Some(params)).await
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Compares a previous snapshot of the authenticated [User]'s [Post]s against their current posts
+        pub async fn diff_since(&self, snapshot: &[Post]) -> Result<PostDiff, ApiError> {
+            self.posts().await.map(|current| diff_post_lists(snapshot, &current))
+        }
+
+        /// Returns a single [Page] of [Post]s associated with the authenticated [User].
+        /// `/me/posts` ignores pagination & always returns every post, so the page is sliced client-side.
+        pub async fn posts_page(&self, page: u64, per_page: u64) -> Result<Page<Post>, ApiError> {
+            let posts = self.posts().await?;
+            let total = posts.len() as u64;
+            let start = page.saturating_sub(1).saturating_mul(per_page);
+            Ok(Page {
+                items: posts
+                    .into_iter()
+                    .skip(usize::try_from(start).unwrap_or(usize::MAX))
+                    .take(usize::try_from(per_page).unwrap_or(usize::MAX))
+                    .collect(),
+                page,
+                per_page,
+                total: Some(total),
+            })
+        }
+
+        /// Returns a [Paginator] over all [Post]s associated with the authenticated [User], yielding `per_page` posts at a time.
+        /// Every page re-fetches the full listing (see [UserHandler::posts_page]), so prefer [UserHandler::posts] when all posts are needed.
+        pub fn stream_posts(&self, per_page: u64) -> Paginator<Post> {
+            let handler = self.clone();
+            Paginator::new(per_page, move |page, per_page| {
+                let handler = handler.clone();
+                async move { handler.posts_page(page, per_page).await }
+            })
+        }
+
         /// Returns the specified [Post]
         pub async fn post(&self, id: &str) -> Result<Post, ApiError> {
             if self.client.is_authenticated() {
@@ -90,6 +198,66 @@ pub mod api_handlers {
             }
         }
 
+        /// Returns the total number of words across every [Post] in all of the authenticated [User]'s [Collection]s
+        pub async fn total_word_count(&self) -> Result<u64, ApiError> {
+            let mut total = 0;
+            for collection in self.collections().await? {
+                total += collection.total_word_count().await?;
+            }
+            Ok(total)
+        }
+
+        /// Exports all of the authenticated [User]'s data in the desired format, returning the raw file contents.
+        /// WriteFreely serves exports as a web route (`/me/export.{ext}`), outside of `/api`.
+        pub async fn export(&self, format: ExportFormat) -> Result<Vec<u8>, ApiError> {
+            if self.client.is_authenticated() {
+                self.client
+                    .api()
+                    .get_web_bytes(format!("/me/export.{}", format.extension()).as_str())
+                    .await
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Exports all of the authenticated [User]'s data in the desired format, writing it to a file
+        pub async fn export_to_file(&self, format: ExportFormat, path: &Path) -> Result<(), ApiError> {
+            let bytes = self.export(format).await?;
+            tokio::fs::write(path, bytes)
+                .await
+                .map_err(|e| ApiError::IoError { reason: e.to_string() })
+        }
+
+        /// Returns all [Channel]s associated with the authenticated [User] (Write.as only)
+        pub async fn channels(&self) -> Result<Vec<Channel>, ApiError> {
+            if self.client.is_authenticated() {
+                self.client
+                    .api()
+                    .get::<Vec<Channel>>("/me/channels")
+                    .await
+                    .map(|mut v| {
+                        v.iter_mut()
+                            .map(|x| x.with_client(self.client.clone()))
+                            .collect()
+                    })
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Returns the specified [Channel] (Write.as only)
+        pub async fn channel(&self, id: &str) -> Result<Channel, ApiError> {
+            if self.client.is_authenticated() {
+                self.client
+                    .api()
+                    .get::<Channel>(format!("/me/channels/{id}").as_str())
+                    .await
+                    .map(|mut v| v.with_client(self.client.clone()))
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
         /// Returns the specified [Collection]
         pub async fn collection(&self, alias: &str) -> Result<Collection, ApiError> {
             if self.client.is_authenticated() {
@@ -127,7 +295,153 @@ pub mod api_handlers {
                 .and_then(|mut p| Ok(p.with_client(self.client.clone())))
         }
 
+        /// Gets a specific [Post] by ID, retrying with a stored post token if the server responds with a 403.
+        /// The fallback is only attempted if a [TokenStore](crate::api_client::TokenStore) is attached to the client (tokens are looked up under [post_token_key]);
+        /// otherwise, or if no token is stored, the original error is returned.
+        pub async fn get_with_fallback(&self, id: &str) -> Result<Post, ApiError> {
+            match self.get(id).await {
+                Err(e) if e.is_forbidden() => {
+                    if let Some(store) = self.client.token_store() {
+                        match store.load(post_token_key(id).as_str())? {
+                            Some(token) => self.get_anonymous(id, token.as_str()).await,
+                            None => Err(e),
+                        }
+                    } else {
+                        Err(e)
+                    }
+                }
+                result => result,
+            }
+        }
+
+        /// Fetches several [Post]s by ID concurrently, running at most `concurrency` requests at once (`0` uses a default of 5).
+        /// Each result in the returned vector corresponds to the ID at the same index in `ids`.
+        pub async fn get_many_by_id(&self, ids: &[&str], concurrency: usize) -> Vec<Result<Post, ApiError>> {
+            let concurrency = if concurrency == 0 { 5 } else { concurrency };
+            let mut results: Vec<(usize, Result<Post, ApiError>)> = futures::stream::iter(ids.iter().enumerate())
+                .map(|(index, id)| async move { (index, self.get(id).await) })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
+            results.sort_by_key(|(index, _)| *index);
+            results.into_iter().map(|(_, result)| result).collect()
+        }
+
+        /// Gets a specific anonymous [Post] by ID, attaching its token so that it can later be updated or deleted
+        pub async fn get_anonymous(&self, id: &str, token: &str) -> Result<Post, ApiError> {
+            let api = self.client.api();
+            let request = api
+                .request(format!("/posts/{id}").as_str(), Method::GET)?
+                .query(&[("token", token)]);
+            if let Ok(response) = request.send().await {
+                api.extract_response::<Post>(response).await.map(|mut p| {
+                    p.token = Some(token.to_string());
+                    p.with_client(self.client.clone())
+                })
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
+
+        /// Returns the most recently published [Post]s across all public collections on the instance (the public timeline)
+        pub async fn recent(&self, limit: u64) -> Result<Vec<Post>, ApiError> {
+            self.timeline(&[("limit", limit.to_string())]).await
+        }
+
+        /// Returns the most recently published public [Post]s with a specific tag
+        pub async fn recent_by_tag(&self, tag: &str, limit: u64) -> Result<Vec<Post>, ApiError> {
+            self.timeline(&[("limit", limit.to_string()), ("tag", tag.trim_start_matches('#').to_string())])
+                .await
+        }
+
+        async fn timeline(&self, query: &[(&str, String)]) -> Result<Vec<Post>, ApiError> {
+            let api = self.client.api();
+            let request = api.request("/posts", Method::GET)?.query(query);
+            if let Ok(response) = request.send().await {
+                api.extract_response::<Vec<Post>>(response).await.map(|mut v| {
+                    v.iter_mut()
+                        .map(|x| x.with_client(self.client.clone()))
+                        .collect()
+                })
+            } else {
+                Err(ApiError::ConnectionError {})
+            }
+        }
+
+        /// Returns all unpublished draft [Post]s belonging to the authenticated user
+        pub async fn drafts(&self) -> Result<Vec<Post>, ApiError> {
+            if self.client.is_authenticated() {
+                self.client
+                    .api()
+                    .get::<Vec<Post>>("/me/posts?draft=true")
+                    .await
+                    .map(|mut v| {
+                        v.iter_mut()
+                            .map(|x| x.with_client(self.client.clone()))
+                            .collect()
+                    })
+            } else {
+                Err(ApiError::LoggedOut {})
+            }
+        }
+
+        /// Returns the most recently created [Post] in a collection, or belonging to the authenticated user if `collection` is `None`.
+        /// If the server ignores the ordering/limit parameters, the newest post is picked from everything it returns.
+        pub async fn latest(&self, collection: Option<&str>) -> Result<Option<Post>, ApiError> {
+            let endpoint = match collection {
+                Some(alias) => format!("/collections/{alias}/posts?order=desc"),
+                None if self.client.is_authenticated() => "/me/posts?order=desc".to_string(),
+                None => return Err(ApiError::LoggedOut {}),
+            };
+
+            self.client
+                .api()
+                .get_paginated::<Post, _>(endpoint.as_str(), (1, 1))
+                .await
+                .map(|page| {
+                    page.items
+                        .into_iter()
+                        .max_by_key(|p| p.created)
+                        .map(|mut p| p.with_client(self.client.clone()))
+                })
+        }
+
+        async fn user_posts(&self) -> Result<Vec<Post>, ApiError> {
+            UserHandler {
+                client: self.client.clone(),
+                current: None,
+            }
+            .posts()
+            .await
+        }
+
+        /// Returns the first of the authenticated user's [Post]s matching a predicate.
+        /// `/me/posts` ignores pagination, so every post is fetched in a single request.
+        pub async fn find(&self, predicate: impl Fn(&Post) -> bool + Send) -> Result<Option<Post>, ApiError> {
+            Ok(self.user_posts().await?.into_iter().find(|p| predicate(p)))
+        }
+
+        /// Returns all of the authenticated user's [Post]s matching a predicate
+        pub async fn find_all(&self, predicate: impl Fn(&Post) -> bool + Send) -> Result<Vec<Post>, ApiError> {
+            Ok(self.user_posts().await?.into_iter().filter(|p| predicate(p)).collect())
+        }
+
+        /// Deletes all of the authenticated user's [Post]s older than `age`. Failing deletions don't stop the process;
+        /// returns the number of posts deleted along with the errors of those that couldn't be.
+        pub async fn delete_older_than(&self, age: Duration) -> Result<(u64, Vec<ApiError>), ApiError> {
+            let mut deleted = 0;
+            let mut errors = Vec::new();
+            for post in self.find_all(|p| p.is_older_than(age)).await? {
+                match post.delete().await {
+                    Ok(()) => deleted += 1,
+                    Err(e) => errors.push(e),
+                }
+            }
+            Ok((deleted, errors))
+        }
+
         /// Creates a [PostCreationBuilder] with the desired body.
+        #[must_use = "this returns a PostCreationBuilder, which does nothing until built & published"]
         pub fn create(&self, body: String) -> PostCreationBuilder {
             PostCreationBuilder::default()
                 .client(Some(self.client.clone()))
@@ -135,24 +449,60 @@ pub mod api_handlers {
                 .clone()
         }
 
+        /// Creates, configures and publishes a [Post] in one step. The `configure` closure receives a [PostCreationBuilder] with the body already set.
+        pub async fn create_and_publish(
+            &self,
+            body: String,
+            configure: impl FnOnce(&mut PostCreationBuilder) -> &mut PostCreationBuilder,
+        ) -> Result<Post, ApiError> {
+            let mut builder = self.create(body);
+            configure(&mut builder)
+                .build()
+                .map_err(Into::<ApiError>::into)?
+                .publish()
+                .await
+        }
+
+        /// Builds & publishes one post per `(body, title)` entry using a [PostTemplate]'s shared settings, in order.
+        /// Stops at the first failure; posts published before it are not rolled back.
+        pub async fn publish_template(&self, template: &PostTemplate, entries: Vec<(String, Option<String>)>) -> Result<Vec<Post>, ApiError> {
+            let mut posts = Vec::new();
+            for builder in template.create_posts(entries) {
+                let post = builder.build().map_err(Into::<ApiError>::into)?;
+                posts.push(self.publish(post).await?);
+            }
+            Ok(posts)
+        }
+
         /// Publishes a previously-made [PostCreation] instance
         pub async fn publish(&self, post: PostCreation) -> Result<Post, ApiError> {
             if let Some(collection) = post.collection.clone() {
                 self.client
                     .api()
-                    .post::<Post, PostCreation>(format!("/collections/{collection}/post").as_str(), Some(post))
+                    .post::<Post, PostCreation>(format!("/collections/{collection}/post").as_str(), Some(post.clone()))
                     .await
-                    .and_then(|mut p| Ok(p.with_client(self.client.clone())))
+                    .and_then(|mut p| {
+                        self.client.store_post_token(&p);
+                        Ok(p.with_client(self.client.clone()))
+                    })
             } else {
                 self.client
                     .api()
-                    .post::<Post, PostCreation>("/posts", Some(post))
+                    .post::<Post, PostCreation>("/posts", Some(post.clone()))
                     .await
-                    .and_then(|mut p| Ok(p.with_client(self.client.clone())))
+                    .and_then(|mut p| {
+                        self.client.store_post_token(&p);
+                        Ok(p.with_client(self.client.clone()))
+                    })
             }
         }
     }
 
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct FollowParameters {
+        pub url: String,
+    }
+
     #[derive(Clone, Debug, Serialize, Deserialize)]
     struct CollectionParameters {
         pub alias: Option<String>,
@@ -195,6 +545,14 @@ pub mod api_handlers {
                 .and_then(|mut v| Ok(v.with_client(self.client.clone())))
         }
 
+        /// Retrieves a [Collection] by its alias, creating it if it doesn't exist
+        pub async fn get_or_create(&self, alias: String, title: Option<String>) -> Result<Collection, ApiError> {
+            match self.get(alias.as_str()).await {
+                Err(e) if e.is_not_found() => self.create(Some(alias), title).await,
+                result => result,
+            }
+        }
+
         /// Retrieves a [Collection] by its alias.
         pub async fn get(&self, alias: &str) -> Result<Collection, ApiError> {
             self.client
@@ -204,4 +562,58 @@ pub mod api_handlers {
                 .and_then(|mut v| Ok(v.with_client(self.client.clone())))
         }
     }
+
+    #[derive(Clone, Debug)]
+    /// Handler for instance administration methods (see [Client::admin])
+    pub struct AdminHandler {
+        client: Client,
+    }
+
+    impl AdminHandler {
+        /// Creates a new [AdminHandler] with a [Client] instance. Prefer [Client::admin], which checks that the client is authenticated.
+        pub fn new(client: Client) -> Self {
+            AdminHandler {
+                client: client.clone(),
+            }
+        }
+
+        /// Returns a single [Page] of every [User] registered on the instance
+        pub async fn list_users(&self, page: u64, per_page: u64) -> Result<Page<User>, ApiError> {
+            self.client
+                .api()
+                .get_paginated::<User, _>("/admin/users", (page, per_page))
+                .await
+        }
+
+        /// Suspends a [User] by username
+        pub async fn suspend_user(&self, username: &str) -> Result<(), ApiError> {
+            self.client
+                .api()
+                .post::<serde_json::Value, ()>(format!("/admin/users/{username}/suspend").as_str(), None)
+                .await
+                .map(|_| ())
+        }
+
+        /// Permanently deletes a [User] by username
+        pub async fn delete_user(&self, username: &str) -> Result<(), ApiError> {
+            self.client
+                .api()
+                .delete(format!("/admin/users/{username}").as_str())
+                .await
+        }
+
+        /// Returns a single [Page] of every [Post] on the instance
+        pub async fn list_posts(&self, page: u64, per_page: u64) -> Result<Page<Post>, ApiError> {
+            self.client
+                .api()
+                .get_paginated::<Post, _>("/admin/posts", (page, per_page))
+                .await
+                .map(|v| v.map(|mut x| x.with_client(self.client.clone())))
+        }
+
+        /// Retrieves aggregate statistics for the instance
+        pub async fn instance_stats(&self) -> Result<InstanceStats, ApiError> {
+            self.client.api().get::<InstanceStats>("/admin/stats").await
+        }
+    }
 }
@@ -1,13 +1,37 @@
 /// This module provides API model definitions & associated methods.
 pub mod api_models {
-    
+    /// Implements `to_json` & `from_json` convenience methods for the listed models
+    #[cfg(feature = "json-helpers")]
+    macro_rules! json_helpers {
+        ($($model:ident),+) => {
+            $(
+                impl $model {
+                    /// Serializes this model to a JSON string
+                    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+                        serde_json::to_string(self)
+                    }
+
+                    /// Deserializes this model from a JSON string
+                    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+                        serde_json::from_str(s)
+                    }
+                }
+            )+
+        };
+    }
 
     /// This module provides models related to [User]
     pub mod users {
+        use std::{
+            fmt,
+            hash::{Hash, Hasher},
+        };
+
         use chrono::{DateTime, Utc};
         use serde_derive::{Deserialize, Serialize};
 
         #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
         /// Base User model
         pub struct User {
             /// Username
@@ -18,21 +42,116 @@ pub mod api_models {
 
             /// Creation D/T (may not be present based on instance settings & associated request)
             pub created: Option<DateTime<Utc>>,
+
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            /// Display name (Write.as only)
+            pub display_name: Option<String>,
+
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            /// Profile bio (Write.as only)
+            pub bio: Option<String>,
+
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            /// Avatar URL (Write.as only)
+            pub avatar: Option<String>,
+
+            #[serde(default, skip_serializing_if = "Option::is_none")]
+            /// Profile links (Write.as only)
+            pub links: Option<Vec<UserLink>>,
+
+            #[serde(default)]
+            /// Whether the user has a Write.as Pro subscription (always `false` on WriteFreely)
+            pub is_pro: bool,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        /// A single link on a [User]'s extended profile
+        pub struct UserLink {
+            /// Link URL
+            pub url: String,
+
+            /// Link title
+            pub title: Option<String>,
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        /// Format of a user data export
+        pub enum ExportFormat {
+            /// All posts & collections as JSON
+            Json,
+
+            /// All posts & collections as a ZIP archive
+            Zip,
+        }
+
+        impl ExportFormat {
+            /// Returns the file extension used by the export endpoint
+            pub fn extension(&self) -> &'static str {
+                match self {
+                    ExportFormat::Json => "json",
+                    ExportFormat::Zip => "zip",
+                }
+            }
+        }
+
+        #[cfg(feature = "json-helpers")]
+        json_helpers!(User);
+
+        /// [User]s are compared by username only
+        impl PartialEq for User {
+            fn eq(&self, other: &Self) -> bool {
+                self.username == other.username
+            }
+        }
+
+        impl Eq for User {}
+
+        /// Consistent with [PartialEq], hashes only the username
+        impl Hash for User {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.username.hash(state);
+            }
+        }
+
+        impl fmt::Display for User {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match &self.email {
+                    Some(email) => write!(f, "@{} ({})", self.username, email),
+                    None => write!(f, "@{}", self.username),
+                }
+            }
         }
     }
 
     /// This module provides models related to [Post]
     pub mod posts {
-        use chrono::{DateTime, Utc};
+        use std::{
+            cmp::Ordering,
+            fmt,
+            hash::{Hash, Hasher},
+            io,
+            path::Path,
+            time::Duration,
+        };
+
+        use chrono::{DateTime, NaiveDate, Utc};
         use derive_builder::Builder;
-        use reqwest::Method;
+        use rand::{distributions::Alphanumeric, Rng};
+        use reqwest::{Method, Url};
+        use serde::{Deserialize as _, Deserializer, Serializer};
         use serde_derive::{Deserialize, Serialize};
+        use sha2::{Digest, Sha256};
 
-        use crate::api_client::{ApiError, Client};
+        use crate::api_client::{ApiError, Client, RequestError};
 
-        use super::collections::{Collection, MovePost, MoveResult};
+        use super::{
+            collections::{Collection, MovePost, MoveResult, PinPost, PinResult},
+            meta::PostMeta,
+            requests::PostToken,
+        };
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+        #[cfg_attr(feature = "strum", derive(strum::EnumIter))]
         /// Enum describing the appearance/font of a post
         pub enum PostAppearance {
             #[serde(rename = "sans")]
@@ -58,8 +177,52 @@ pub mod api_models {
             Code,
         }
 
+        impl Default for PostAppearance {
+            /// Returns [PostAppearance::Serif], WriteFreely's documented default font
+            fn default() -> Self {
+                PostAppearance::Serif
+            }
+        }
+
+        impl PostAppearance {
+            /// Returns an iterator over every [PostAppearance] variant
+            pub fn variants() -> impl Iterator<Item = PostAppearance> {
+                [
+                    PostAppearance::SansSerif,
+                    PostAppearance::Serif,
+                    PostAppearance::Wrap,
+                    PostAppearance::Mono,
+                    PostAppearance::Code,
+                ]
+                .into_iter()
+            }
+
+            /// Checks if this appearance is intended for technical/code posts ([PostAppearance::Code] or [PostAppearance::Mono])
+            pub fn is_code_like(&self) -> bool {
+                matches!(self, PostAppearance::Code | PostAppearance::Mono)
+            }
+
+            /// Checks if this appearance is intended for prose ([PostAppearance::SansSerif], [PostAppearance::Serif] or [PostAppearance::Wrap])
+            pub fn is_prose_like(&self) -> bool {
+                matches!(self, PostAppearance::SansSerif | PostAppearance::Serif | PostAppearance::Wrap)
+            }
+
+            /// Returns the CSS `font-family` stack WriteFreely uses for this appearance
+            pub fn font_stack(&self) -> &'static str {
+                match self {
+                    PostAppearance::SansSerif => "\"Open Sans\", \"Segoe UI\", Tahoma, Arial, sans-serif",
+                    PostAppearance::Serif => "Lora, \"Palatino Linotype\", \"Book Antiqua\", \"New York\", \"DejaVu serif\", serif",
+                    PostAppearance::Wrap | PostAppearance::Mono | PostAppearance::Code => "Hack, consolas, Menlo-Regular, Menlo, Monaco, monospace",
+                }
+            }
+        }
+
         #[derive(Clone, Debug, Serialize, Deserialize, Builder)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
         /// Struct describing a pending update to a [Post]
+        ///
+        /// `None` fields are omitted from the request, leaving the post's current value unchanged.
+        /// To clear a post's title, set `title` to `Some("".to_string())` rather than `None`.
         pub struct PostUpdate {
             #[serde(skip_serializing)]
             /// [Client] instance
@@ -69,26 +232,113 @@ pub mod api_models {
             /// Post ID
             pub id: String,
 
+            #[serde(skip_serializing_if = "Option::is_none")]
             /// Post token, if not owned
             pub token: Option<String>,
 
             /// New post body
             pub body: String,
 
+            #[serde(skip_serializing_if = "Option::is_none")]
             /// New post title
             pub title: Option<String>,
 
+            #[serde(skip_serializing_if = "Option::is_none")]
             /// New post font
             pub font: Option<PostAppearance>,
 
+            #[serde(skip_serializing_if = "Option::is_none")]
             /// New post language
             pub lang: Option<String>,
 
             /// New post RTL
             pub rtl: bool,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// New draft status, if supported by the server
+            pub draft: Option<bool>,
         }
 
         impl PostUpdate {
+            /// Creates a [PostUpdate] from an original [Post] and a set of new values. Any `None` value is treated as
+            /// "unchanged", and is filled in with the original post's current value (as the API may not support partial updates).
+            pub fn diff_only(
+                original: &Post,
+                body: &str,
+                title: Option<&str>,
+                font: Option<PostAppearance>,
+                lang: Option<&str>,
+                rtl: Option<bool>,
+            ) -> PostUpdate {
+                PostUpdate {
+                    client: original.client.clone(),
+                    id: original.id.clone(),
+                    token: original.token.clone(),
+                    body: body.to_string(),
+                    title: title.map(|t| t.to_string()).or(original.title.clone()),
+                    font: font.or(original.appearance.clone()),
+                    lang: lang.map(|l| l.to_string()).or(original.language.clone()),
+                    rtl: rtl.unwrap_or(original.rtl),
+                    draft: None,
+                }
+            }
+
+            /// Checks whether this update would leave `original` unchanged
+            pub fn no_op(&self, original: &Post) -> bool {
+                !self.any_changed(original)
+            }
+
+            /// Checks whether this update changes `original`'s body
+            pub fn body_changed(&self, original: &Post) -> bool {
+                self.body != original.body
+            }
+
+            /// Checks whether this update changes `original`'s title (an unset `title` is never a change)
+            pub fn title_changed(&self, original: &Post) -> bool {
+                self.title.is_some() && self.title != original.title
+            }
+
+            /// Checks whether this update changes `original`'s font (an unset `font` is never a change)
+            pub fn font_changed(&self, original: &Post) -> bool {
+                self.font.is_some() && self.font != original.appearance
+            }
+
+            /// Checks whether this update changes `original`'s language (an unset `lang` is never a change)
+            pub fn lang_changed(&self, original: &Post) -> bool {
+                self.lang.is_some() && self.lang != original.language
+            }
+
+            /// Checks whether this update changes `original`'s text direction
+            pub fn rtl_changed(&self, original: &Post) -> bool {
+                self.rtl != original.rtl
+            }
+
+            /// Checks whether this update changes `original`'s draft status (an unset `draft` is never a change)
+            pub fn draft_changed(&self, original: &Post) -> bool {
+                self.draft.is_some_and(|d| Some(d) != original.draft)
+            }
+
+            /// Checks whether this update changes any of `original`'s fields
+            pub fn any_changed(&self, original: &Post) -> bool {
+                !self.changed_fields(original).is_empty()
+            }
+
+            /// Returns the names of every field this update changes, relative to `original`
+            pub fn changed_fields(&self, original: &Post) -> Vec<&'static str> {
+                [
+                    ("body", self.body_changed(original)),
+                    ("title", self.title_changed(original)),
+                    ("font", self.font_changed(original)),
+                    ("lang", self.lang_changed(original)),
+                    ("rtl", self.rtl_changed(original)),
+                    ("draft", self.draft_changed(original)),
+                ]
+                .into_iter()
+                .filter_map(|(name, changed)| changed.then_some(name))
+                .collect()
+            }
+
             /// Dispatches an update request to the server.
             pub async fn update(&self) -> Result<Post, ApiError> {
                 if let Some(client) = self.client.clone() {
@@ -106,9 +356,39 @@ pub mod api_models {
             }
         }
 
+        fn serialize_collection_alias<S: Serializer>(
+            collection: &Option<Collection>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match collection {
+                Some(c) => serializer.serialize_some(&c.alias),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum CollectionOrAlias {
+            Alias(String),
+            Collection(Box<Collection>),
+        }
+
+        fn deserialize_collection<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Collection>, D::Error> {
+            Ok(
+                Option::<CollectionOrAlias>::deserialize(deserializer)?.map(|c| match c {
+                    CollectionOrAlias::Alias(alias) => Collection::new(alias.as_str(), alias.as_str()),
+                    CollectionOrAlias::Collection(c) => *c,
+                }),
+            )
+        }
+
         #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
         /// Main struct describing a single Post
         pub struct Post {
+            #[serde(skip)]
             ///
             pub client: Option<Client>,
             ///
@@ -131,10 +411,23 @@ pub mod api_models {
             pub tags: Vec<String>,
             ///
             pub views: Option<u64>,
-            ///
+            #[serde(
+                default,
+                serialize_with = "serialize_collection_alias",
+                deserialize_with = "deserialize_collection"
+            )]
+            /// Parent collection. Serialized as just its alias, to keep payloads small and avoid leaking the attached [Client]
             pub collection: Option<Collection>,
             ///
             pub token: Option<String>,
+
+            #[serde(default)]
+            /// Draft status, if supported by the server
+            pub draft: Option<bool>,
+
+            #[serde(default)]
+            /// Position in the parent collection's pinned posts, if pinned (and if reported by the server)
+            pub pinned_position: Option<u64>,
         }
 
         impl Post {
@@ -145,6 +438,7 @@ pub mod api_models {
             }
 
             /// Returns a [PostUpdateBuilder] initialized with a [Client], the correct ID, and the specified body text
+            #[must_use = "this returns a PostUpdateBuilder, which does nothing until built & sent"]
             pub fn build_update(&self, body: String) -> PostUpdateBuilder {
                 PostUpdateBuilder::default()
                     .client(self.client.clone())
@@ -155,6 +449,9 @@ pub mod api_models {
 
             /// Dispatches an update with an existing [PostUpdate]
             pub async fn update(&self, update: PostUpdate) -> Result<Post, ApiError> {
+                if update.no_op(self) {
+                    return Ok(self.clone());
+                }
                 if let Some(client) = self.client.clone() {
                     client
                         .api()
@@ -169,20 +466,31 @@ pub mod api_models {
                 }
             }
 
-            /// Deletes this post
+            /// Deletes this post. If the post has a token (ie, it was created anonymously), the token is always sent along with the request:
+            /// first as a query parameter, then in the request body if the server rejects the former with a 403.
             pub async fn delete(&self) -> Result<(), ApiError> {
                 if let Some(client) = self.client.clone() {
+                    let endpoint = format!("/posts/{}", self.id);
                     let mut request = client
                         .api()
-                        .request(format!("/posts/{}", self.id).as_str(), Method::DELETE)
-                        .unwrap();
-                    if !client.is_authenticated() && self.token.is_some() {
-                        request = request.query(&[("token", self.token.clone().unwrap())]);
+                        .request(endpoint.as_str(), Method::DELETE)?;
+                    if let Some(token) = self.token.clone() {
+                        request = request.query(&[("token", token)]);
                     }
-                    if let Ok(result) = request.send().await {
-                        client.api().extract_response(result).await
+                    let result = if let Ok(result) = request.send().await {
+                        client.api().extract_empty(result).await
                     } else {
                         Err(ApiError::ConnectionError {})
+                    };
+
+                    match (result, self.token.clone()) {
+                        (Err(ApiError::Request { error: RequestError { code: 403, .. } }), Some(token)) => {
+                            client
+                                .api()
+                                .delete_with_body(endpoint.as_str(), Some(PostToken { token }))
+                                .await
+                        }
+                        (result, _) => result,
                     }
                 } else {
                     Err(ApiError::UsageError {})
@@ -213,466 +521,1223 @@ pub mod api_models {
                     Err(ApiError::UsageError {})
                 }
             }
-        }
-
-        #[derive(Clone, Debug, Serialize, Deserialize, Builder)]
-        /// Post creation struct
-        pub struct PostCreation {
-            #[serde(skip_serializing)]
-            /// [Client] instance
-            pub client: Option<Client>,
-
-            #[serde(skip_serializing)]
-            /// Collection to post to, if desired
-            pub collection: Option<String>,
 
-            /// Post body
-            pub body: String,
+            /// Returns the public URL of this post (under the client's base path, if any), if a [Client] is available
+            pub fn url(&self) -> Option<String> {
+                let path = match (self.collection.clone(), self.slug.clone()) {
+                    (Some(collection), Some(slug)) => format!("/{}/{slug}", collection.alias),
+                    _ => format!("/{}", self.id),
+                };
+                self.client.as_ref()?.api().web_url(path.as_str()).ok().map(String::from)
+            }
 
-            /// Post title
-            pub title: Option<String>,
+            /// Returns a `<script>` tag that embeds this post in another website. Fails with [ApiError::UsageError] if no [Client] is available.
+            pub fn embed_code(&self) -> Result<String, ApiError> {
+                let client = self.client.as_ref().ok_or(ApiError::UsageError {})?;
+                match (self.collection.is_some(), self.slug.is_some()) {
+                    (true, true) => embed_script(client, self.url().ok_or(ApiError::UsageError {})?.as_str()),
+                    _ => embed_script(client, self.id.as_str()),
+                }
+            }
 
-            /// Post font
-            pub font: Option<PostAppearance>,
+            /// Returns the first `max_chars` characters of the post body, with whitespace collapsed.
+            /// If the body was truncated, an ellipsis is appended.
+            pub fn excerpt(&self, max_chars: usize) -> String {
+                let text = self.body.split_whitespace().collect::<Vec<&str>>().join(" ");
+                if text.chars().count() <= max_chars {
+                    text
+                } else {
+                    format!("{}…", text.chars().take(max_chars).collect::<String>().trim_end())
+                }
+            }
 
-            /// Post language
-            pub lang: Option<String>,
+            /// Returns a stable hash (lowercase hex SHA-256) of this post's body & title, for detecting content changes
+            pub fn content_hash(&self) -> String {
+                let mut hasher = Sha256::new();
+                hasher.update(self.body.as_bytes());
+                hasher.update(self.title.clone().unwrap_or_default().as_bytes());
+                hex::encode(hasher.finalize())
+            }
 
-            /// Post RTL
-            pub rtl: Option<bool>,
+            /// Returns OpenGraph metadata describing this post
+            pub fn opengraph_meta(&self) -> PostMeta {
+                PostMeta {
+                    og_title: self.title.clone().unwrap_or_default(),
+                    og_description: Some(self.excerpt(200)),
+                    og_url: self.url(),
+                    og_type: "article",
+                }
+            }
 
-            /// Specific post creation DT
-            pub created: Option<DateTime<Utc>>,
-        }
+            /// Checks if this post is an unpublished draft
+            pub fn is_draft(&self) -> bool {
+                self.draft.unwrap_or(false)
+            }
 
-        impl PostCreation {
-            /// Publishes the described post to the server
+            /// Publishes this post, if it is currently a draft
             pub async fn publish(&self) -> Result<Post, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    if let Some(collection) = self.collection.clone() {
-                        client
-                            .api()
-                            .post::<Post, PostCreation>(
-                                format!("/collections/{collection}/post").as_str(),
-                                Some(self.clone()),
-                            )
-                            .await
-                            .and_then(|mut v| Ok(v.with_client(client.clone())))
-                    } else {
-                        client
-                            .api()
-                            .post::<Post, PostCreation>("/posts", Some(self.clone()))
-                            .await
-                            .and_then(|mut v| Ok(v.with_client(client.clone())))
-                    }
+                let mut update = PostUpdate::diff_only(self, &self.body, None, None, None, None);
+                update.draft = Some(false);
+                self.update(update).await
+            }
+
+            /// Mirrors this post onto another instance, returning the new post. If the post belongs to a collection, it is published
+            /// into the collection with the same alias on the target instance (which is created if it doesn't exist yet).
+            /// If the target client isn't authenticated, the post is published anonymously.
+            pub async fn sync_to(&self, target_client: &Client) -> Result<Post, ApiError> {
+                let mut creation = PostCreation::from(self.clone());
+                creation.client = Some(target_client.clone());
+                if let (Some(collection), true) = (&self.collection, target_client.is_authenticated()) {
+                    target_client
+                        .collections()
+                        .get_or_create(collection.alias.clone(), Some(collection.title.clone()))
+                        .await?;
                 } else {
-                    Err(ApiError::UsageError {})
+                    creation.collection = None;
                 }
+                creation.publish().await
             }
-        }
-    }
 
-    #[doc(hidden)]
-    pub mod responses {
-        use std::fmt::Debug;
+            fn parent_collection(&self) -> Result<Collection, ApiError> {
+                match (self.client.clone(), self.collection.clone()) {
+                    (Some(client), Some(mut collection)) => Ok(collection.with_client(client)),
+                    _ => Err(ApiError::UsageError {}),
+                }
+            }
 
-        use super::users::User;
-        use serde_derive::{Deserialize, Serialize};
-        use serde_json::Value;
+            /// Pins this post to its [Collection], optionally at a specific position
+            pub async fn pin_to_collection(&self, position: Option<u64>) -> Result<PinResult, ApiError> {
+                let pin = match position {
+                    Some(position) => PinPost::new_at_position(&self.id, position),
+                    None => PinPost::new(&self.id),
+                };
+                self.parent_collection()?
+                    .pin_posts(&[pin])
+                    .await
+                    .and_then(|v| match v.first() {
+                        Some(Ok(result)) | Some(Err(result)) => Ok(result.clone()),
+                        None => Err(ApiError::UnknownError {}),
+                    })
+            }
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        pub struct Login {
-            pub access_token: String,
-            pub user: User,
-        }
+            /// Unpins this post from its [Collection]
+            pub async fn unpin_from_collection(&self) -> Result<PinResult, ApiError> {
+                self.parent_collection()?
+                    .unpin_posts(std::slice::from_ref(&self.id))
+                    .await
+                    .and_then(|v| match v.first() {
+                        Some(Ok(result)) | Some(Err(result)) => Ok(result.clone()),
+                        None => Err(ApiError::UnknownError {}),
+                    })
+            }
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        pub struct ResponseModel {
-            pub code: u16,
-            pub data: Value,
-        }
-    }
+            /// Generates a URL slug from text: lowercased, with every run of non-alphanumeric characters replaced by a single `-`
+            pub fn generate_slug(text: &str) -> String {
+                text.to_lowercase()
+                    .split(|c: char| !c.is_alphanumeric())
+                    .filter(|part| !part.is_empty())
+                    .collect::<Vec<&str>>()
+                    .join("-")
+            }
 
-    #[doc(hidden)]
-    pub mod requests {
-        use serde_derive::{Deserialize, Serialize};
+            /// Predicts the slug this post will be published under, from its title (or ID if untitled).
+            ///
+            /// This is only an estimate: the server assigns the real slug when a post is added to a collection, and may
+            /// differ (eg. by deduplicating against existing slugs or transliterating non-ASCII titles). Use [Post::final_slug] after publication.
+            pub fn estimated_slug(&self) -> String {
+                Post::generate_slug(self.title.as_deref().unwrap_or(&self.id))
+            }
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        pub struct Login {
-            pub alias: String,
-            pub pass: String,
-        }
-    }
+            /// Returns the definitive slug of a published post, falling back to its ID if the server didn't assign one
+            pub fn final_slug(&self) -> &str {
+                self.slug.as_deref().unwrap_or(&self.id)
+            }
 
-    /// This module provides models related to [Collection]
-    pub mod collections {
-        use derive_builder::Builder;
-        use serde_derive::{Deserialize, Serialize};
-        use serde_repr::{Deserialize_repr, Serialize_repr};
+            /// Returns the post body rendered as sanitized HTML. Without the `markdown` feature, the raw Markdown is returned HTML-escaped.
+            pub fn body_as_html(&self) -> String {
+                #[cfg(feature = "markdown")]
+                {
+                    utils::render_markdown(self.body.as_str())
+                }
 
-        use crate::api_client::{ApiError, Client};
+                #[cfg(not(feature = "markdown"))]
+                {
+                    utils::escape_html(self.body.as_str())
+                }
+            }
 
-        use super::posts::Post;
+            /// Returns the `(text, url)` pairs of every link in the post body: inline (`[text](url)`) & reference (`[text][ref]`)
+            /// Markdown links, and bare URLs (whose text is the URL itself). Image links are only included if `include_images` is set.
+            /// Relative URLs are resolved against the post's collection URL, if available.
+            pub fn anchor_links(&self, include_images: bool) -> Vec<(String, String)> {
+                let base = self.collection.clone().and_then(|mut c| {
+                    if c.client.is_none() {
+                        c.client = self.client.clone();
+                    }
+                    c.url().and_then(|url| Url::parse(url.as_str()).ok())
+                });
+                utils::markdown_links(self.body.as_str(), include_images)
+                    .into_iter()
+                    .map(|(text, url)| match (&base, Url::parse(url.as_str())) {
+                        (Some(base), Err(_)) => match base.join(url.as_str()) {
+                            Ok(absolute) => (text, absolute.to_string()),
+                            Err(_) => (text, url),
+                        },
+                        _ => (text, url),
+                    })
+                    .collect()
+            }
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        /// A struct describing a post to move into a collection
-        pub struct MovePost {
-            /// Post ID
-            pub id: String,
+            /// Returns the unique fediverse handles mentioned in the post body (`@user` or `@user@instance.tld`), in order of appearance
+            pub fn mentions(&self) -> Vec<String> {
+                let chars: Vec<char> = self.body.chars().collect();
+                let mut mentions: Vec<String> = Vec::new();
+                let mut i = 0;
+                while i < chars.len() {
+                    if chars[i] == '@' && (i == 0 || !chars[i - 1].is_alphanumeric()) {
+                        let user: String = chars[i + 1..].iter().take_while(|c| c.is_alphanumeric() || **c == '_').collect();
+                        if user.is_empty() {
+                            i += 1;
+                            continue;
+                        }
+                        let mut handle = format!("@{user}");
+                        i += 1 + user.chars().count();
+                        if chars.get(i) == Some(&'@') {
+                            let domain: String = chars[i + 1..]
+                                .iter()
+                                .take_while(|c| c.is_alphanumeric() || **c == '.' || **c == '-')
+                                .collect();
+                            let domain = domain.trim_end_matches(['.', '-']);
+                            if domain.contains('.') {
+                                handle = format!("{handle}@{domain}");
+                                i += 1 + domain.chars().count();
+                            }
+                        }
+                        if !mentions.contains(&handle) {
+                            mentions.push(handle);
+                        }
+                    } else {
+                        i += 1;
+                    }
+                }
+                mentions
+            }
 
-            /// Post token, if post isn't owned
-            pub token: Option<String>,
-        }
+            /// Checks whether the post body mentions a specific handle (case-insensitive, with or without the leading `@`)
+            pub fn has_mention(&self, handle: &str) -> bool {
+                let handle = handle.trim_start_matches('@').to_lowercase();
+                self.mentions()
+                    .iter()
+                    .any(|m| m.trim_start_matches('@').to_lowercase() == handle)
+            }
 
-        impl MovePost {
-            /// Creates a new MovePost with just an ID
-            pub fn new(id: &str) -> Self {
-                MovePost {
-                    id: id.to_string(),
-                    token: None,
+            /// Returns the unique `#hashtags` in the post body (without the leading `#`), in order of appearance
+            pub fn hashtags(&self) -> Vec<String> {
+                let chars: Vec<char> = self.body.chars().collect();
+                let mut hashtags: Vec<String> = Vec::new();
+                for (i, c) in chars.iter().enumerate() {
+                    if *c == '#' && (i == 0 || !(chars[i - 1].is_alphanumeric() || chars[i - 1] == '#' || chars[i - 1] == '&')) {
+                        let tag: String = chars[i + 1..].iter().take_while(|c| c.is_alphanumeric() || **c == '_').collect();
+                        if tag.chars().any(|c| c.is_alphabetic()) && !hashtags.contains(&tag) {
+                            hashtags.push(tag);
+                        }
+                    }
                 }
+                hashtags
             }
 
-            /// Creates a new MovePost with an ID and token
-            pub fn new_with_token(id: &str, token: &str) -> Self {
-                MovePost {
-                    id: id.to_string(),
-                    token: Some(token.to_string()),
-                }
+            /// Estimates the reading level of the post body using the Flesch-Kincaid formulas (after stripping common Markdown syntax)
+            pub fn reading_level(&self) -> ReadingLevel {
+                ReadingLevel::from_text(utils::strip_markdown(self.body.as_str()).as_str())
             }
-        }
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        #[serde(untagged)]
-        /// Describes the result of a single post move operation
-        pub enum MoveResult {
-            /// Successful operation
-            Success { 
-                /// Operation status code
-                code: u32, 
+            /// Returns the time elapsed since this post was created, if known. Posts created in the future have an age of zero.
+            pub fn age(&self) -> Option<Duration> {
+                self.created
+                    .map(|created| (Utc::now() - created).to_std().unwrap_or(Duration::ZERO))
+            }
 
-                /// Affected [Post]
-                post: Post 
-            },
+            /// Checks whether this post was created at least `duration` ago. Posts without a creation date are never considered older.
+            pub fn is_older_than(&self, duration: Duration) -> bool {
+                self.age().is_some_and(|age| age >= duration)
+            }
 
-            /// Failed operation
-            Error { 
-                /// Operation status code
-                code: u32, 
+            /// Checks whether this post was created less than `duration` ago. Posts without a creation date are never considered newer.
+            pub fn is_newer_than(&self, duration: Duration) -> bool {
+                self.age().is_some_and(|age| age < duration)
+            }
 
-                /// Operation status text
-                error_msg: String 
-            },
+            /// Returns the number of whitespace-separated words in the post body
+            pub fn word_count(&self) -> usize {
+                self.body.split_whitespace().count()
+            }
+        }
+
+        #[derive(Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        struct RawPostSummary {
+            id: String,
+            #[serde(default)]
+            slug: Option<String>,
+            #[serde(default)]
+            title: Option<String>,
+            #[serde(default)]
+            created: Option<DateTime<Utc>>,
+            #[serde(default)]
+            views: Option<u64>,
+            #[serde(default)]
+            tags: Vec<String>,
+            #[serde(default)]
+            collection: Option<CollectionOrAlias>,
+            #[serde(default)]
+            word_count: Option<u64>,
+            #[serde(default)]
+            body: Option<String>,
+        }
+
+        impl From<RawPostSummary> for PostSummary {
+            fn from(raw: RawPostSummary) -> Self {
+                PostSummary {
+                    id: raw.id,
+                    slug: raw.slug,
+                    title: raw.title,
+                    created: raw.created,
+                    views: raw.views,
+                    tags: raw.tags,
+                    collection: raw.collection.map(|c| match c {
+                        CollectionOrAlias::Alias(alias) => alias,
+                        CollectionOrAlias::Collection(c) => c.alias,
+                    }),
+                    word_count: raw
+                        .word_count
+                        .or(raw.body.map(|b| b.split_whitespace().count() as u64))
+                        .unwrap_or(0),
+                }
+            }
         }
 
         #[derive(Clone, Debug, Serialize, Deserialize)]
-        /// A struct describing how to pin or unpin a post to a collection
-        pub struct PinPost {
+        #[serde(from = "RawPostSummary")]
+        /// Lightweight description of a [Post] without its body, for listings
+        pub struct PostSummary {
             /// Post ID
             pub id: String,
 
-            #[serde(skip_serializing_if = "Option::is_none")]
-            /// Pin position (should not be used with `unpin`)
-            pub postion: Option<u64>
-        }
+            /// Post slug, if in a collection
+            pub slug: Option<String>,
 
-        impl PinPost {
-            /// Creates a new PinPost with an ID
-            pub fn new(id: &str) -> Self {
-                PinPost {
-                    id: id.to_string(),
-                    postion: None
+            /// Post title
+            pub title: Option<String>,
+
+            /// Creation D/T
+            pub created: Option<DateTime<Utc>>,
+
+            /// View count
+            pub views: Option<u64>,
+
+            /// Post tags
+            pub tags: Vec<String>,
+
+            /// Alias of the parent collection, if any
+            pub collection: Option<String>,
+
+            /// Number of words in the post body (computed by the server if available, otherwise from the body if it was sent)
+            pub word_count: u64,
+        }
+
+        impl From<&Post> for PostSummary {
+            fn from(post: &Post) -> Self {
+                PostSummary {
+                    id: post.id.clone(),
+                    slug: post.slug.clone(),
+                    title: post.title.clone(),
+                    created: post.created,
+                    views: post.views,
+                    tags: post.tags.clone(),
+                    collection: post.collection.as_ref().map(|c| c.alias.clone()),
+                    word_count: post.word_count() as u64,
                 }
             }
+        }
 
-            /// Creates a new PinPost with an ID and a position
-            pub fn new_at_position(id: &str, position: u64) -> Self {
-                PinPost {
-                    id: id.to_string(),
-                    postion: Some(position),
-                }
+        impl PostSummary {
+            /// Fetches the full [Post] this summary describes
+            pub async fn full_post(&self, client: &Client) -> Result<Post, ApiError> {
+                client.posts().get(self.id.as_str()).await
             }
         }
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        #[serde(untagged)]
-        /// Describes the result of a single pin/unpin operation
-        pub enum PinResult {
-            /// Successful operation
-            Success { 
-                /// Operation status code
-                code: u32, 
-                /// Post ID
-                id: String 
-            },
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        /// Broad reading level category, based on the Flesch-Kincaid grade level
+        pub enum ReadingLevelCategory {
+            /// Grade level below 6
+            Elementary,
 
-            /// Failed operation
-            Error { 
-                /// Operation status code
-                code: u32, 
-                /// Operation status text
-                error_msg: String 
-            },
-        }
+            /// Grade level 6 to 8
+            MiddleSchool,
 
-        #[derive(Clone, Debug, Serialize, Deserialize)]
-        /// A struct describing a single Collection entity
-        pub struct Collection {
-            ///
-            pub client: Option<Client>,
-            ///
-            pub alias: String,
-            ///
-            pub title: String,
-            ///
-            pub description: Option<String>,
-            ///
-            pub style_sheet: Option<String>,
-            ///
-            pub public: bool,
-            ///
-            pub views: Option<u64>,
-            ///
-            pub verification_link: Option<String>,
-            ///
-            pub total_posts: Option<u64>,
+            /// Grade level 9 to 12
+            HighSchool,
+
+            /// Grade level 13 to 16
+            College,
+
+            /// Grade level above 16
+            Professional,
         }
 
-        impl Collection {
-            #[doc(hidden)]
-            pub fn with_client(&mut self, client: Client) -> Self {
-                self.client = Some(client);
-                self.clone()
-            }
+        #[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+        /// Estimated complexity of a text (see [Post::reading_level])
+        pub struct ReadingLevel {
+            /// Flesch-Kincaid grade level (approximate US school grade needed to understand the text)
+            pub flesch_kincaid_grade: f32,
 
-            /// Creates a [CollectionUpdateBuilder] with defaults set
-            pub fn build_update(&self) -> CollectionUpdateBuilder {
-                CollectionUpdateBuilder::default()
-                    .alias(Some(self.alias.clone()))
-                    .client(self.client.clone())
-                    .clone()
-            }
-            
-            /// Updates a collection from an existing [CollectionUpdate]
-            pub async fn update(&self, update: CollectionUpdate) -> Result<Collection, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    client
-                        .api()
-                        .post::<Collection, CollectionUpdate>(
-                            format!("/collections/{}", self.alias).as_str(),
-                            Some(update.clone()),
-                        )
-                        .await
-                        .and_then(|mut p| Ok(p.with_client(client.clone())))
-                } else {
-                    Err(ApiError::UsageError {})
+            /// Flesch reading ease score (higher is easier, typically 0-100)
+            pub flesch_reading_ease: f32,
+
+            /// Category derived from the grade level
+            pub level: ReadingLevelCategory,
+        }
+
+        impl ReadingLevel {
+            /// Computes the reading level of plain text. Syllables are approximated by counting vowel groups.
+            pub fn from_text(text: &str) -> Self {
+                let words: Vec<&str> = text
+                    .split_whitespace()
+                    .filter(|w| w.chars().any(|c| c.is_alphanumeric()))
+                    .collect();
+                if words.is_empty() {
+                    return ReadingLevel {
+                        flesch_kincaid_grade: 0.0,
+                        flesch_reading_ease: 100.0,
+                        level: ReadingLevelCategory::Elementary,
+                    };
                 }
-            }
 
-            /// Deletes this [Collection]
-            pub async fn delete(&self) -> Result<(), ApiError> {
-                if let Some(client) = self.client.clone() {
-                    client
-                        .api()
-                        .delete(format!("/collections/{}", self.alias).as_str())
-                        .await
-                } else {
-                    Err(ApiError::UsageError {})
+                let sentences = text
+                    .split(['.', '!', '?'])
+                    .filter(|s| s.chars().any(|c| c.is_alphanumeric()))
+                    .count()
+                    .max(1) as f32;
+                let syllables: usize = words.iter().map(|w| ReadingLevel::syllables(w)).sum();
+                let words_per_sentence = words.len() as f32 / sentences;
+                let syllables_per_word = syllables as f32 / words.len() as f32;
+
+                let grade = 0.39 * words_per_sentence + 11.8 * syllables_per_word - 15.59;
+                let level = match grade {
+                    g if g < 6.0 => ReadingLevelCategory::Elementary,
+                    g if g < 9.0 => ReadingLevelCategory::MiddleSchool,
+                    g if g < 13.0 => ReadingLevelCategory::HighSchool,
+                    g if g < 17.0 => ReadingLevelCategory::College,
+                    _ => ReadingLevelCategory::Professional,
+                };
+                ReadingLevel {
+                    flesch_kincaid_grade: grade,
+                    flesch_reading_ease: 206.835 - 1.015 * words_per_sentence - 84.6 * syllables_per_word,
+                    level,
                 }
             }
 
-            /// Returns all [Post]s belonging to this collection
-            pub async fn get_posts(&self) -> Result<Vec<Post>, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    client
-                        .api()
-                        .get::<Vec<Post>>(format!("/collections/{}/posts", self.alias).as_str())
-                        .await
-                        .and_then(|mut v| {
-                            Ok(v.iter_mut()
-                                .map(|x| x.with_client(client.clone()))
-                                .collect())
-                        })
-                } else {
-                    Err(ApiError::UsageError {})
+            fn syllables(word: &str) -> usize {
+                let word: Vec<char> = word
+                    .to_lowercase()
+                    .chars()
+                    .filter(|c| c.is_alphabetic())
+                    .collect();
+                let is_vowel = |c: &char| "aeiouy".contains(*c);
+                let mut count = 0;
+                let mut previous_vowel = false;
+                for c in &word {
+                    let vowel = is_vowel(c);
+                    if vowel && !previous_vowel {
+                        count += 1;
+                    }
+                    previous_vowel = vowel;
                 }
+                if count > 1 && word.last() == Some(&'e') {
+                    count -= 1;
+                }
+                count.max(1)
             }
+        }
 
-            /// Returns a single [Post] belonging to this collection
-            pub async fn get_post(&self, slug: String) -> Result<Post, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    client
-                        .api()
-                        .get::<Post>(format!("/collections/{}/posts/{}", self.alias, slug).as_str())
-                        .await
-                        .and_then(|mut v| Ok(v.with_client(client.clone())))
-                } else {
-                    Err(ApiError::UsageError {})
-                }
+        #[cfg(feature = "json-helpers")]
+        json_helpers!(Post, PostCreation);
+
+        /// [Post]s are compared by ID only
+        impl PartialEq for Post {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
             }
+        }
 
-            /// Moves a set of [Post]s into this collection
-            pub async fn take_posts(
-                &self,
-                posts: &[MovePost],
-            ) -> Result<Vec<Result<MoveResult, MoveResult>>, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    let result = client
-                        .api()
-                        .post::<Vec<MoveResult>, &[MovePost]>(
-                            format!("/collections/{}/collect", self.alias).as_str(),
-                            Some(posts),
-                        )
-                        .await;
-                    match result {
-                        Ok(results) => Ok(results
-                            .iter()
-                            .map::<Result<MoveResult, MoveResult>, _>(|r| match r {
-                                MoveResult::Success { code, post } => Ok(MoveResult::Success {
-                                    code: code.clone(),
-                                    post: post.clone().with_client(client.clone()),
-                                }),
-                                MoveResult::Error { code, error_msg } => Err(MoveResult::Error {
-                                    code: code.clone(),
-                                    error_msg: error_msg.clone(),
-                                }),
-                            })
-                            .collect()),
-                        Err(e) => Err(e),
-                    }
-                } else {
-                    Err(ApiError::UsageError {})
-                }
+        impl Eq for Post {}
+
+        /// Consistent with [PartialEq], hashes only the ID
+        impl Hash for Post {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.id.hash(state);
             }
+        }
 
-            /// Pins a set of [Post]s in this collection
-            pub async fn pin_posts(
-                &self,
-                posts: &[PinPost],
-            ) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    let result = client
-                        .api()
-                        .post::<Vec<PinResult>, &[PinPost]>(
-                            format!("/collections/{}/pin", self.alias).as_str(),
-                            Some(posts),
-                        )
-                        .await;
-                    match result {
-                        Ok(results) => Ok(results
-                            .iter()
-                            .map::<Result<PinResult, PinResult>, _>(|r| match r {
-                                PinResult::Success { code, id } => Ok(PinResult::Success {
-                                    code: code.clone(),
-                                    id: id.clone()
-                                }),
-                                PinResult::Error { code, error_msg } => Err(PinResult::Error {
-                                    code: code.clone(),
-                                    error_msg: error_msg.clone(),
-                                }),
-                            })
-                            .collect()),
-                        Err(e) => Err(e),
-                    }
-                } else {
-                    Err(ApiError::UsageError {})
-                }
+        /// [Post]s are ordered chronologically by creation date, with undated posts last & ties broken by ID.
+        ///
+        /// Note that [PartialEq] only compares IDs, so `Ord` is consistent with it only as long as copies of the same
+        /// post share a creation date (as they do when fetched from the server). Two copies of a post with different
+        /// creation dates are unequal under `Ord`, and are kept apart by `sort()` & `BTreeSet` but merged by `HashSet`.
+        impl Ord for Post {
+            fn cmp(&self, other: &Self) -> Ordering {
+                (self.created.is_none(), self.created, &self.id).cmp(&(other.created.is_none(), other.created, &other.id))
             }
+        }
 
-            /// Unpins a set of [Post]s from this collection
-            pub async fn unpin_posts(&self, posts: &[String]) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
-                if let Some(client) = self.client.clone() {
-                    let result = client
-                        .api()
-                        .post::<Vec<PinResult>, Vec<PinPost>>(
-                            format!("/collections/{}/unpin", self.alias).as_str(),
-                            Some(posts.iter().map(|v| PinPost::new(v.as_str())).collect()),
-                        )
-                        .await;
-                    match result {
-                        Ok(results) => Ok(results
-                            .iter()
-                            .map::<Result<PinResult, PinResult>, _>(|r| match r {
-                                PinResult::Success { code, id } => Ok(PinResult::Success {
-                                    code: code.clone(),
-                                    id: id.clone()
-                                }),
-                                PinResult::Error { code, error_msg } => Err(PinResult::Error {
-                                    code: code.clone(),
-                                    error_msg: error_msg.clone(),
-                                }),
-                            })
-                            .collect()),
-                        Err(e) => Err(e),
-                    }
-                } else {
-                    Err(ApiError::UsageError {})
-                }
+        impl PartialOrd for Post {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
             }
         }
 
-        #[derive(Clone, Debug, Serialize_repr, Deserialize_repr)]
-        #[repr(u8)]
-        /// Enum describing a collection's visibility
-        pub enum CollectionVisibility {
-            ///
-            Unlisted = 0,
-            ///
-            Public = 1,
-            ///
-            Private = 2,
-            ///
-            Password = 4,
+        impl fmt::Display for Post {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(
+                    f,
+                    "{} [{}] - {} ({} words)",
+                    self.title.as_deref().unwrap_or("Untitled"),
+                    self.id,
+                    self.created
+                        .map_or("unknown date".to_string(), |d| d.format("%Y-%m-%d").to_string()),
+                    self.word_count()
+                )
+            }
         }
 
         #[derive(Clone, Debug, Serialize, Deserialize, Builder)]
-        /// Struct describing a collection update
-        pub struct CollectionUpdate {
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// Post creation struct
+        ///
+        /// Only `body` is required when using [PostCreationBuilder]; all other fields default to `None`.
+        /// [PostCreationBuilder::build] returns a [PostCreationBuilderError] if `body` was never set.
+        pub struct PostCreation {
             #[serde(skip_serializing)]
+            #[builder(default)]
             /// [Client] instance
             pub client: Option<Client>,
 
             #[serde(skip_serializing)]
-            /// Collection alias to update
-            pub alias: Option<String>,
+            #[builder(default)]
+            /// Collection to post to, if desired
+            pub collection: Option<String>,
 
-            /// New title
+            /// Post body
+            pub body: String,
+
+            #[builder(default)]
+            /// Post title
             pub title: Option<String>,
 
-            /// New description
-            pub description: Option<String>,
+            #[builder(default)]
+            /// Post font
+            pub font: Option<PostAppearance>,
 
-            /// New style sheet
-            pub style_sheet: Option<String>,
+            #[builder(default)]
+            /// Post language
+            pub lang: Option<String>,
 
-            /// New script (Write.as only)
-            pub script: Option<String>,
+            #[builder(default)]
+            /// Post RTL
+            pub rtl: Option<bool>,
 
-            /// New visibility level
-            pub visibility: Option<CollectionVisibility>,
+            #[builder(default)]
+            /// Specific post creation DT
+            pub created: Option<DateTime<Utc>>,
 
-            /// New password (only [CollectionVisibility::Password])
-            pub pass: Option<String>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// Whether the post should be listed publicly, if supported by the server
+            pub listed: Option<bool>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// Whether the post should be created as an unpublished draft, if supported by the server
+            pub draft: Option<bool>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// Canonical URL used by link previews, if supported by the server (ignored by Write.as & WriteFreely)
+            pub link_preview_url: Option<String>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// Image URL used by link previews, if supported by the server (ignored by Write.as & WriteFreely)
+            pub preview_image_url: Option<String>,
 
-            /// Whether to enable Mathjax support
-            pub mathjax: bool,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// Client-supplied token for an anonymous post, if supported by the server. Only sent with the request; the published [Post] carries whatever token the server returns.
+            pub token: Option<String>,
         }
 
-        impl CollectionUpdate {
-            /// Publish the update request to the server
-            pub async fn update(&self) -> Result<Collection, ApiError> {
+        impl From<Post> for PostCreation {
+            /// Creates a [PostCreation] duplicating an existing post's content, in the same collection (if any)
+            fn from(post: Post) -> Self {
+                PostCreation {
+                    client: post.client,
+                    collection: post.collection.map(|c| c.alias),
+                    body: post.body,
+                    title: post.title,
+                    font: post.appearance,
+                    lang: post.language,
+                    rtl: Some(post.rtl),
+                    created: post.created,
+                    listed: None,
+                    draft: None,
+                    link_preview_url: None,
+                    preview_image_url: None,
+                    token: None,
+                }
+            }
+        }
+
+        impl From<Post> for PostUpdate {
+            /// Creates a [PostUpdate] that leaves the post unchanged, to be modified before sending
+            fn from(post: Post) -> Self {
+                PostUpdate::diff_only(&post, post.body.as_str(), None, None, None, None)
+            }
+        }
+
+        impl From<&Post> for PostUpdateBuilder {
+            /// Creates a [PostUpdateBuilder] pre-filled with all of the post's updatable fields
+            fn from(post: &Post) -> Self {
+                PostUpdateBuilder::default()
+                    .client(post.client.clone())
+                    .id(post.id.clone())
+                    .token(post.token.clone())
+                    .body(post.body.clone())
+                    .title(post.title.clone())
+                    .font(post.appearance.clone())
+                    .lang(post.language.clone())
+                    .rtl(post.rtl)
+                    .clone()
+            }
+        }
+
+        impl From<PostCreationBuilderError> for ApiError {
+            fn from(_: PostCreationBuilderError) -> Self {
+                ApiError::UsageError {}
+            }
+        }
+
+        impl PostCreationBuilder {
+            /// Sets the post title. Equivalent to `title(Some(title))`.
+            pub fn title_required(&mut self, title: String) -> &mut Self {
+                self.title(Some(title))
+            }
+
+            /// Sets the post body, only if it hasn't already been set
+            pub fn body_if_empty_default(&mut self, default: String) -> &mut Self {
+                if self.body.is_none() {
+                    self.body = Some(default);
+                }
+                self
+            }
+
+            /// Explicitly sets the post font to WriteFreely's default ([PostAppearance::default])
+            pub fn default_appearance(&mut self) -> &mut Self {
+                self.font(Some(PostAppearance::default()))
+            }
+
+            /// Targets the post at the collection with the given alias. Equivalent to `collection(Some(alias.to_string()))`.
+            pub fn in_collection(&mut self, alias: &str) -> &mut Self {
+                self.collection(Some(alias.to_string()))
+            }
+
+            /// Creates the post outside of any collection. Equivalent to `collection(None)`.
+            pub fn anonymous(&mut self) -> &mut Self {
+                self.collection(None)
+            }
+
+            /// Sets the link preview image. Equivalent to `preview_image_url(Some(url.to_string()))`.
+            pub fn with_preview_image(&mut self, url: &str) -> &mut Self {
+                self.preview_image_url(Some(url.to_string()))
+            }
+
+            /// Pre-assigns an anonymous post token. Equivalent to `token(Some(token))`.
+            pub fn with_token(&mut self, token: String) -> &mut Self {
+                self.token(Some(token))
+            }
+
+            /// Pre-assigns a random 32-character alphanumeric anonymous post token (see [PostCreation::token])
+            pub fn generate_token(&mut self) -> &mut Self {
+                let token: String = rand::thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(32)
+                    .map(char::from)
+                    .collect();
+                self.with_token(token)
+            }
+        }
+
+        #[derive(Clone, Debug, Default, Serialize, Deserialize)]
+        /// Settings shared by a series of posts (eg. chapters of a book), used to pre-configure [PostCreationBuilder]s
+        pub struct PostTemplate {
+            /// Collection to post to, if any
+            pub collection: Option<String>,
+
+            /// Post font
+            pub font: Option<PostAppearance>,
+
+            /// Post language
+            pub lang: Option<String>,
+
+            /// Post RTL
+            pub rtl: Option<bool>,
+        }
+
+        impl PostTemplate {
+            /// Returns a [PostCreationBuilder] pre-configured with this template's settings
+            #[must_use = "this returns a PostCreationBuilder, which does nothing until built & published"]
+            pub fn create_post(&self, body: String, title: Option<String>) -> PostCreationBuilder {
+                PostCreationBuilder::default()
+                    .body(body)
+                    .title(title)
+                    .collection(self.collection.clone())
+                    .font(self.font.clone())
+                    .lang(self.lang.clone())
+                    .rtl(self.rtl)
+                    .clone()
+            }
+
+            /// Returns a pre-configured [PostCreationBuilder] for each `(body, title)` entry, in order
+            #[must_use = "this returns PostCreationBuilders, which do nothing until built & published"]
+            pub fn create_posts(&self, entries: Vec<(String, Option<String>)>) -> Vec<PostCreationBuilder> {
+                entries
+                    .into_iter()
+                    .map(|(body, title)| self.create_post(body, title))
+                    .collect()
+            }
+        }
+
+        impl PostCreation {
+            /// Returns a [PostCreationBuilder] for a Markdown document, with settings taken from its front matter (if any).
+            ///
+            /// Front matter is a block of flat `key: value` lines delimited by `---`, at the very start of the document.
+            /// The recognised keys are `title`, `lang`/`language`, `font`/`appearance`, `rtl`, `collection`/`blog` &
+            /// `created`/`date` (RFC 3339 or `YYYY-MM-DD`); others are ignored. Without front matter, the whole document becomes the body.
+            pub fn from_markdown_str(markdown: &str) -> Result<PostCreationBuilder, io::Error> {
+                let invalid = |key: &str, value: &str| io::Error::new(io::ErrorKind::InvalidData, format!("invalid front matter value for `{key}`: {value}"));
+                let mut builder = PostCreationBuilder::default();
+                let markdown = markdown.trim_start_matches('\u{feff}');
+                let mut lines = markdown.lines();
+                if lines.next().map(str::trim_end) != Some("---") {
+                    return Ok(builder.body(markdown.to_string()).clone());
+                }
+                let front_matter: Vec<&str> = lines.by_ref().take_while(|l| !matches!(l.trim_end(), "---" | "...")).collect();
+                let body: Vec<&str> = lines.collect();
+                if front_matter.len() + 1 == markdown.lines().count() {
+                    // Unterminated front matter, so treat the whole document as the body
+                    return Ok(builder.body(markdown.to_string()).clone());
+                }
+
+                for line in front_matter {
+                    let Some((key, value)) = line.split_once(':') else {
+                        continue;
+                    };
+                    let key = key.trim().to_lowercase();
+                    let value = value.trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+                    if value.is_empty() {
+                        continue;
+                    }
+                    match key.as_str() {
+                        "title" => builder.title(Some(value)),
+                        "lang" | "language" => builder.lang(Some(value)),
+                        "font" | "appearance" => builder.font(Some(
+                            serde_json::from_value(serde_json::Value::String(value.clone())).map_err(|_| invalid(&key, &value))?,
+                        )),
+                        "rtl" => builder.rtl(Some(value.parse().map_err(|_| invalid(&key, &value))?)),
+                        "collection" | "blog" => builder.collection(Some(value)),
+                        "created" | "date" => builder.created(Some(
+                            DateTime::parse_from_rfc3339(&value)
+                                .map(|d| d.with_timezone(&Utc))
+                                .or_else(|_| NaiveDate::parse_from_str(&value, "%Y-%m-%d").map(|d| d.and_time(Default::default()).and_utc()))
+                                .map_err(|_| invalid(&key, &value))?,
+                        )),
+                        _ => &mut builder,
+                    };
+                }
+                Ok(builder.body(body.join("\n").trim_start_matches('\n').to_string()).clone())
+            }
+
+            /// Reads a Markdown file into a [PostCreationBuilder] (see [PostCreation::from_markdown_str] for front matter handling)
+            pub async fn from_file(path: &Path) -> Result<PostCreationBuilder, io::Error> {
+                PostCreation::from_markdown_str(tokio::fs::read_to_string(path).await?.as_str())
+            }
+
+            /// Reads a Markdown document from standard input into a [PostCreationBuilder] (see [PostCreation::from_markdown_str])
+            pub async fn from_stdin() -> Result<PostCreationBuilder, io::Error> {
+                let mut markdown = String::new();
+                tokio::io::AsyncReadExt::read_to_string(&mut tokio::io::stdin(), &mut markdown).await?;
+                PostCreation::from_markdown_str(markdown.as_str())
+            }
+
+            /// Checks whether `lang` is a valid BCP 47 language tag.
+            /// With the `language-tags` feature this uses a full parser; otherwise it approximates `lang[-Script][-REGION]` (eg. `en`, `zh-Hant-TW`, `es-419`).
+            pub fn validate_language(lang: &str) -> bool {
+                #[cfg(feature = "language-tags")]
+                {
+                    language_tags::LanguageTag::parse(lang).is_ok()
+                }
+
+                #[cfg(not(feature = "language-tags"))]
+                {
+                    let mut parts = lang.split('-').peekable();
+                    let language = parts.next().unwrap_or_default();
+                    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_lowercase()) {
+                        return false;
+                    }
+                    if let Some(script) = parts.peek() {
+                        let mut chars = script.chars();
+                        if script.len() == 4
+                            && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+                            && chars.all(|c| c.is_ascii_lowercase())
+                        {
+                            parts.next();
+                        }
+                    }
+                    if let Some(region) = parts.next() {
+                        let valid_region = (region.len() == 2 && region.chars().all(|c| c.is_ascii_uppercase()))
+                            || (region.len() == 3 && region.chars().all(|c| c.is_ascii_digit()));
+                        if !valid_region {
+                            return false;
+                        }
+                    }
+                    parts.next().is_none()
+                }
+            }
+
+            /// Appends a Markdown image (`![](url)`) for each URL to the end of the body, separated from it by a blank line.
+            /// An empty slice leaves the body unchanged.
+            pub fn with_images(&mut self, urls: &[&str]) -> Self {
+                if !urls.is_empty() {
+                    let images: Vec<String> = urls.iter().map(|u| format!("![]({u})")).collect();
+                    self.body = format!("{}\n\n{}", self.body, images.join("\n"));
+                }
+                self.clone()
+            }
+
+            /// Inserts a Markdown image (`![](url)`) into the body at a character (not byte) position, clamped to the end of the body
+            pub fn insert_image_at(&mut self, url: &str, position: usize) -> Self {
+                let index = self.body.char_indices().nth(position).map_or(self.body.len(), |(i, _)| i);
+                self.body.insert_str(index, format!("![]({url})").as_str());
+                self.clone()
+            }
+
+            /// Checks the body size against the limit configured on the attached [Client] (see [Client::with_max_post_bytes]).
+            /// The check is skipped if there is no client or no configured limit.
+            pub fn body_length_check(&self) -> Result<(), ApiError> {
+                match self.client.as_ref().and_then(|c| c.max_post_bytes()) {
+                    Some(limit) if self.body.len() > limit => Err(ApiError::ValidationError {
+                        errors: vec![format!("Body exceeds {limit} bytes (actual: {} bytes)", self.body.len())],
+                    }),
+                    _ => Ok(()),
+                }
+            }
+
+            /// Checks this post for errors the server would reject, returning an [ApiError::ValidationError] describing each one
+            pub fn validate(&self) -> Result<(), ApiError> {
+                let mut errors = Vec::new();
+                if let Err(ApiError::ValidationError { errors: length_errors }) = self.body_length_check() {
+                    errors.extend(length_errors);
+                }
+                if let Some(lang) = &self.lang {
+                    if !PostCreation::validate_language(lang) {
+                        errors.push(format!("Invalid language tag: {lang}"));
+                    }
+                }
+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(ApiError::ValidationError { errors })
+                }
+            }
+
+            /// Returns a [PostCreationBuilder] for an unpublished draft with the desired body
+            #[must_use = "this returns a PostCreationBuilder, which does nothing until built & published"]
+            pub fn draft(body: String) -> PostCreationBuilder {
+                PostCreationBuilder::default()
+                    .body(body)
+                    .draft(Some(true))
+                    .clone()
+            }
+
+            /// Publishes the described post to the server
+            pub async fn publish(&self) -> Result<Post, ApiError> {
                 if let Some(client) = self.client.clone() {
-                    if let Some(alias) = self.alias.clone() {
+                    if let Some(collection) = self.collection.clone() {
                         client
                             .api()
-                            .post::<Collection, CollectionUpdate>(
-                                format!("/collections/{}", alias).as_str(),
+                            .post::<Post, PostCreation>(
+                                format!("/collections/{collection}/post").as_str(),
                                 Some(self.clone()),
                             )
                             .await
-                            .and_then(|mut p| Ok(p.with_client(client.clone())))
+                            .and_then(|mut v| {
+                                client.store_post_token(&v);
+                                Ok(v.with_client(client.clone()))
+                            })
                     } else {
-                        Err(ApiError::UsageError {})
+                        client
+                            .api()
+                            .post::<Post, PostCreation>("/posts", Some(self.clone()))
+                            .await
+                            .and_then(|mut v| {
+                                client.store_post_token(&v);
+                                Ok(v.with_client(client.clone()))
+                            })
+                    }
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+        }
+
+        /// Builds the `<script>` tag loading the instance's embed script for `post` (an ID or URL), percent-encoding `post` in the query
+        pub(crate) fn embed_script(client: &Client, post: &str) -> Result<String, ApiError> {
+            let mut url = client.api().web_url("/js/embed.js")?;
+            url.query_pairs_mut().append_pair("post", post);
+            Ok(format!("<script src=\"{url}\"></script>"))
+        }
+
+        /// Pure helper functions for working with sets of [Post]s locally
+        pub mod utils {
+            use std::{cmp::Reverse, collections::HashMap};
+
+            use super::{Post, PostAppearance};
+
+            /// Renders Markdown to HTML, sanitized to prevent XSS from untrusted content
+            #[cfg(feature = "markdown")]
+            pub fn render_markdown(markdown: &str) -> String {
+                let mut html = String::new();
+                pulldown_cmark::html::push_html(&mut html, pulldown_cmark::Parser::new(markdown));
+                ammonia::clean(html.as_str())
+            }
+
+            /// Escapes the characters HTML treats specially (`&`, `<`, `>`, `"` & `'`), so `text` can be embedded in HTML as-is
+            pub fn escape_html(text: &str) -> String {
+                let mut escaped = String::with_capacity(text.len());
+                for c in text.chars() {
+                    match c {
+                        '&' => escaped.push_str("&amp;"),
+                        '<' => escaped.push_str("&lt;"),
+                        '>' => escaped.push_str("&gt;"),
+                        '"' => escaped.push_str("&quot;"),
+                        '\'' => escaped.push_str("&#39;"),
+                        _ => escaped.push(c),
+                    }
+                }
+                escaped
+            }
+
+            /// Strips common Markdown syntax (fenced code blocks, link targets & formatting characters), leaving readable text
+            pub fn strip_markdown(markdown: &str) -> String {
+                let mut in_code_block = false;
+                let mut lines = Vec::new();
+                for line in markdown.lines() {
+                    if line.trim_start().starts_with("```") {
+                        in_code_block = !in_code_block;
+                        continue;
+                    }
+                    if in_code_block {
+                        continue;
+                    }
+
+                    let mut text = String::new();
+                    let mut chars = line.chars().peekable();
+                    while let Some(c) = chars.next() {
+                        match c {
+                            ']' if chars.peek() == Some(&'(') => {
+                                for c in chars.by_ref() {
+                                    if c == ')' {
+                                        break;
+                                    }
+                                }
+                            }
+                            '!' if chars.peek() == Some(&'[') => {}
+                            '#' | '*' | '_' | '`' | '>' | '~' | '[' | ']' => {}
+                            c => text.push(c),
+                        }
+                    }
+                    lines.push(text.trim().to_string());
+                }
+                lines.join("\n")
+            }
+
+            /// Extracts the `(text, url)` pairs of every inline link, reference link & bare URL in a Markdown document, in order.
+            /// Image links are only included if `include_images` is set, and fenced code blocks are ignored.
+            pub fn markdown_links(markdown: &str, include_images: bool) -> Vec<(String, String)> {
+                let mut references: HashMap<String, String> = HashMap::new();
+                let mut lines = Vec::new();
+                let mut in_code_block = false;
+                for line in markdown.lines() {
+                    if line.trim_start().starts_with("```") {
+                        in_code_block = !in_code_block;
+                    } else if !in_code_block {
+                        match reference_definition(line) {
+                            Some((label, url)) => {
+                                references.entry(label.to_lowercase()).or_insert(url);
+                            }
+                            None => lines.push(line),
+                        }
+                    }
+                }
+
+                let mut links = Vec::new();
+                for line in lines {
+                    let chars: Vec<char> = line.chars().collect();
+                    let mut i = 0;
+                    while i < chars.len() {
+                        let image = chars[i] == '!' && chars.get(i + 1) == Some(&'[');
+                        let start = if image { i + 1 } else { i };
+                        if let Some(close) = (chars[start] == '[').then(|| closing_bracket(&chars, start)).flatten() {
+                            let text: String = chars[start + 1..close].iter().collect();
+                            let delimited = |open: char, close_char: char| {
+                                (chars.get(close + 1) == Some(&open))
+                                    .then(|| chars[close + 2..].iter().position(|c| *c == close_char))
+                                    .flatten()
+                                    .map(|length| (chars[close + 2..close + 2 + length].iter().collect::<String>(), close + 3 + length))
+                            };
+                            let target = if let Some((inner, end)) = delimited('(', ')') {
+                                let url = inner.split_whitespace().next().unwrap_or("").trim_matches(['<', '>']);
+                                Some((url.to_string(), end))
+                            } else if let Some((label, end)) = delimited('[', ']') {
+                                let label = if label.is_empty() { text.clone() } else { label };
+                                references.get(&label.to_lowercase()).map(|url| (url.clone(), end))
+                            } else {
+                                references.get(&text.to_lowercase()).map(|url| (url.clone(), close + 1))
+                            };
+                            if let Some((url, end)) = target.filter(|(url, _)| !url.is_empty()) {
+                                if include_images || !image {
+                                    links.push((text, url));
+                                }
+                                i = end;
+                                continue;
+                            }
+                        }
+
+                        if (i == 0 || !chars[i - 1].is_alphanumeric()) && (starts_with(&chars[i..], "http://") || starts_with(&chars[i..], "https://")) {
+                            let url: String = chars[i..]
+                                .iter()
+                                .take_while(|c| !c.is_whitespace() && !matches!(c, '<' | '>' | '"' | '\'' | ')' | ']'))
+                                .collect();
+                            let url = url.trim_end_matches(['.', ',', ';', ':', '!', '?']).to_string();
+                            i += url.chars().count();
+                            links.push((url.clone(), url));
+                            continue;
+                        }
+                        i += 1;
+                    }
+                }
+                links
+            }
+
+            /// Parses a reference link definition line (`[label]: url "optional title"`) into `(label, url)`
+            fn reference_definition(line: &str) -> Option<(String, String)> {
+                let (label, rest) = line.trim_start().strip_prefix('[')?.split_once("]:")?;
+                let url = rest.split_whitespace().next()?.trim_matches(['<', '>']);
+                (!label.is_empty() && !url.is_empty()).then(|| (label.to_string(), url.to_string()))
+            }
+
+            /// Finds the index of the `]` matching the `[` at `open`, accounting for nested brackets
+            fn closing_bracket(chars: &[char], open: usize) -> Option<usize> {
+                let mut depth = 0;
+                for (i, c) in chars.iter().enumerate().skip(open) {
+                    match c {
+                        '[' => depth += 1,
+                        ']' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return Some(i);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                None
+            }
+
+            fn starts_with(chars: &[char], prefix: &str) -> bool {
+                prefix.chars().enumerate().all(|(i, c)| chars.get(i) == Some(&c))
+            }
+
+            /// Returns all posts with the given appearance
+            pub fn filter_posts_by_appearance<'a>(posts: &'a [Post], appearance: &PostAppearance) -> Vec<&'a Post> {
+                posts
+                    .iter()
+                    .filter(|p| p.appearance.as_ref() == Some(appearance))
+                    .collect()
+            }
+
+            /// Sorts posts in place by creation date. Posts without a creation date are always placed at the end.
+            pub fn sort_posts_by_date(posts: &mut [Post], ascending: bool) {
+                posts.sort_by(|a, b| match (a.created, b.created) {
+                    (Some(a), Some(b)) if ascending => a.cmp(&b),
+                    (Some(a), Some(b)) => b.cmp(&a),
+                    (a, b) => b.is_some().cmp(&a.is_some()),
+                });
+            }
+
+            /// Sorts posts in place by view count, most viewed first. Posts without a view count are treated as having 0 views.
+            pub fn sort_posts_by_views(posts: &mut [Post]) {
+                posts.sort_by_key(|p| Reverse(p.views.unwrap_or(0)));
+            }
+
+            /// Returns the `limit` most viewed posts, most viewed first. Posts without a view count are treated as having 0 views.
+            pub fn most_viewed(mut posts: Vec<Post>, limit: u64) -> Vec<Post> {
+                sort_posts_by_views(&mut posts);
+                posts.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+                posts
+            }
+
+            /// Sorts posts in place by title. Posts without a title are treated as having an empty title.
+            pub fn sort_posts_by_title(posts: &mut [Post]) {
+                posts.sort_by(|a, b| {
+                    a.title
+                        .as_deref()
+                        .unwrap_or("")
+                        .cmp(b.title.as_deref().unwrap_or(""))
+                });
+            }
+        }
+    }
+
+    /// This module provides models related to [Channel](channels::Channel)s (Write.as only)
+    pub mod channels {
+        use chrono::{DateTime, Utc};
+        use reqwest::Method;
+        use serde_derive::{Deserialize, Serialize};
+
+        use crate::api_client::{ApiError, Client};
+
+        use super::posts::Post;
+
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        /// Enum describing the external service a [Channel] posts to
+        pub enum ChannelType {
+            /// Medium
+            Medium,
+            /// Tumblr
+            Tumblr,
+            /// Twitter
+            Twitter,
+            /// Mastodon
+            Mastodon,
+            /// Any service not known to this library
+            #[serde(other)]
+            Other,
+        }
+
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        /// Enum describing the delivery status of a [Post] sent through a [Channel]
+        pub enum DeliveryStatus {
+            /// Delivery hasn't been attempted yet
+            Pending,
+            /// Successfully delivered
+            Delivered,
+            /// Delivery failed
+            Failed,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// A [Post] sent through a [Channel], along with its delivery status
+        pub struct ChannelPost {
+            /// Delivered post
+            pub post: Post,
+
+            /// Delivery status
+            pub status: DeliveryStatus,
+
+            #[serde(default)]
+            /// Error message, if delivery failed
+            pub error_msg: Option<String>,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// A struct describing a cross-posting channel (Write.as Pro only)
+        pub struct Channel {
+            #[serde(skip)]
+            /// [Client] instance
+            pub client: Option<Client>,
+
+            /// Channel ID
+            pub id: String,
+
+            /// Display name
+            pub name: String,
+
+            #[serde(rename = "type")]
+            /// External service type
+            pub type_: ChannelType,
+
+            /// URL of the connected account
+            pub url: String,
+
+            /// Whether the external account is currently connected
+            pub connected: bool,
+
+            /// Last successful sync D/T
+            pub last_synced: Option<DateTime<Utc>>,
+        }
+
+        impl Channel {
+            #[doc(hidden)]
+            pub fn with_client(&mut self, client: Client) -> Self {
+                self.client = Some(client);
+                self.clone()
+            }
+
+            /// Returns every [Post] sent through this channel, along with its delivery status
+            pub async fn deliveries(&self) -> Result<Vec<ChannelPost>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .get::<Vec<ChannelPost>>(format!("/me/channels/{}/posts", self.id).as_str())
+                        .await
+                        .map(|mut v| {
+                            v.iter_mut()
+                                .map(|x| {
+                                    x.post = x.post.with_client(client.clone());
+                                    x.clone()
+                                })
+                                .collect()
+                        })
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns every [Post] sent through this channel
+            pub async fn posts(&self) -> Result<Vec<Post>, ApiError> {
+                self.deliveries()
+                    .await
+                    .map(|v| v.into_iter().map(|x| x.post).collect())
+            }
+
+            /// Retries delivery of a [Post] through this channel
+            pub async fn resend(&self, post_id: &str) -> Result<(), ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let api = client.api();
+                    let request = api.request(
+                        format!("/me/channels/{}/posts/{post_id}/resend", self.id).as_str(),
+                        Method::POST,
+                    )?;
+                    if let Ok(response) = request.send().await {
+                        api.extract_empty(response).await
+                    } else {
+                        Err(ApiError::ConnectionError {})
                     }
                 } else {
                     Err(ApiError::UsageError {})
@@ -680,4 +1745,2958 @@ pub mod api_models {
             }
         }
     }
+
+    /// This module provides models related to the WriteFreely instance itself
+    pub mod instance {
+        use serde_derive::{Deserialize, Serialize};
+
+        use super::responses::NodeInfo;
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// Metadata describing a WriteFreely instance
+        pub struct InstanceInfo {
+            /// Server software name
+            pub software: String,
+
+            /// Server software version
+            pub version: String,
+
+            /// Whether new users can register
+            pub allow_registration: bool,
+
+            /// Whether ActivityPub federation is enabled
+            pub federation_enabled: bool,
+
+            #[serde(default)]
+            /// Maximum post size in bytes, if reported by the server
+            pub max_post_size: Option<usize>,
+
+            #[serde(default)]
+            /// Languages posts may be written in, if restricted by the server
+            pub allowed_languages: Option<Vec<String>>,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// Aggregate instance statistics, available to administrators
+        pub struct InstanceStats {
+            /// Number of registered users
+            pub total_users: u64,
+
+            /// Number of posts, including anonymous posts
+            pub total_posts: u64,
+
+            /// Number of collections (blogs)
+            pub total_collections: u64,
+
+            #[serde(default)]
+            /// Number of suspended users, if reported by the server
+            pub suspended_users: Option<u64>,
+        }
+
+        impl From<NodeInfo> for InstanceInfo {
+            fn from(value: NodeInfo) -> Self {
+                InstanceInfo {
+                    software: value.software.name,
+                    version: value.software.version,
+                    allow_registration: value.open_registrations,
+                    federation_enabled: value.protocols.iter().any(|p| p == "activitypub"),
+                    max_post_size: None,
+                    allowed_languages: None,
+                }
+            }
+        }
+    }
+
+    /// This module provides models related to social sharing metadata
+    pub mod meta {
+        use serde_derive::Serialize;
+
+        #[derive(Clone, Debug, Serialize)]
+        /// OpenGraph metadata, for building link previews
+        pub struct OpenGraphMeta {
+            /// `og:title`
+            pub og_title: String,
+
+            /// `og:description`
+            pub og_description: Option<String>,
+
+            /// `og:url`
+            pub og_url: Option<String>,
+
+            /// `og:type`
+            pub og_type: &'static str,
+        }
+
+        /// OpenGraph metadata describing a [Collection](super::collections::Collection)
+        pub type CollectionMeta = OpenGraphMeta;
+
+        /// OpenGraph metadata describing a [Post](super::posts::Post)
+        pub type PostMeta = OpenGraphMeta;
+    }
+
+    /// This module provides models related to paginated listings
+    pub mod pagination {
+        use std::{
+            collections::VecDeque,
+            fmt,
+            future::Future,
+            pin::Pin,
+            sync::Mutex,
+            task::{Context, Poll},
+        };
+
+        use futures::{stream::BoxStream, Stream, StreamExt};
+        use serde_derive::{Deserialize, Serialize};
+
+        use crate::api_client::ApiError;
+
+        /// Default number of items fetched per page by convenience methods that page through listings
+        pub const DEFAULT_PAGE_SIZE: u64 = 25;
+
+        #[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+        /// Query parameters describing which page of a listing to fetch
+        pub struct PageParams {
+            /// Page number (starting at 1)
+            pub page: u64,
+
+            #[serde(rename = "limit")]
+            /// Maximum number of items per page
+            pub per_page: u64,
+        }
+
+        impl PageParams {
+            /// Creates a new [PageParams] instance
+            pub fn new(page: u64, per_page: u64) -> Self {
+                PageParams { page, per_page }
+            }
+        }
+
+        impl From<(u64, u64)> for PageParams {
+            fn from((page, per_page): (u64, u64)) -> Self {
+                PageParams::new(page, per_page)
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        /// A single page of a paginated listing
+        pub struct Page<T> {
+            /// Items on this page
+            pub items: Vec<T>,
+
+            /// Page number (starting at 1)
+            pub page: u64,
+
+            /// Maximum number of items per page
+            pub per_page: u64,
+
+            /// Total number of items, if reported by the server
+            pub total: Option<u64>,
+        }
+
+        impl<T> Page<T> {
+            /// Maps every item on this page
+            pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Page<U> {
+                Page {
+                    items: self.items.into_iter().map(f).collect(),
+                    page: self.page,
+                    per_page: self.per_page,
+                    total: self.total,
+                }
+            }
+        }
+
+        /// An async [Stream] over every item of a paginated listing. Pages are fetched lazily, one at a time.
+        pub struct Paginator<T> {
+            inner: BoxStream<'static, Result<T, ApiError>>,
+        }
+
+        /// Iteration state of a [Paginator]
+        struct PageCursor<F, T> {
+            fetch: F,
+            page: u64,
+            buffer: VecDeque<T>,
+            previous: Vec<T>,
+            fetched: u64,
+            page_size: Option<u64>,
+            done: bool,
+        }
+
+        impl<T: Clone + PartialEq + Send + 'static> Paginator<T> {
+            /// Creates a new [Paginator] from a function that fetches a single [Page], given a page number & page size.
+            ///
+            /// Iteration stops after the first error, an empty page, a page repeating the previous one, once the
+            /// total reported by the server has been reached, or after a page shorter than the page size the server
+            /// actually used (taken from the first page, as some endpoints ignore the requested `per_page`).
+            pub fn new<F, Fut>(per_page: u64, fetch: F) -> Self
+            where
+                F: Fn(u64, u64) -> Fut + Send + 'static,
+                Fut: Future<Output = Result<Page<T>, ApiError>> + Send + 'static,
+            {
+                let state = PageCursor {
+                    fetch,
+                    page: 1,
+                    buffer: VecDeque::new(),
+                    previous: Vec::new(),
+                    fetched: 0,
+                    page_size: None,
+                    done: false,
+                };
+                let inner = futures::stream::unfold(state, move |mut cursor| async move {
+                    loop {
+                        if let Some(item) = cursor.buffer.pop_front() {
+                            return Some((Ok(item), cursor));
+                        }
+
+                        if cursor.done {
+                            return None;
+                        }
+
+                        match (cursor.fetch)(cursor.page, per_page).await {
+                            Ok(result) => {
+                                let len = result.items.len() as u64;
+                                if len == 0 || result.items == cursor.previous {
+                                    cursor.done = true;
+                                    continue;
+                                }
+
+                                let page_size = *cursor.page_size.get_or_insert(len.min(per_page));
+                                cursor.fetched += len;
+                                cursor.done = len < page_size || result.total.is_some_and(|total| cursor.fetched >= total);
+                                cursor.buffer.extend(result.items.iter().cloned());
+                                cursor.previous = result.items;
+                                cursor.page += 1;
+                            }
+                            Err(e) => {
+                                cursor.done = true;
+                                return Some((Err(e), cursor));
+                            }
+                        }
+                    }
+                })
+                .boxed();
+
+                Paginator { inner }
+            }
+        }
+
+        impl<T> Stream for Paginator<T> {
+            type Item = Result<T, ApiError>;
+
+            fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                self.inner.poll_next_unpin(cx)
+            }
+        }
+
+        /// Lazily-initialized [Paginator] stored inside a model, for models that implement [Stream] themselves.
+        /// Cloning produces a fresh (unstarted) state, so each clone iterates from the first page.
+        pub(crate) struct StreamState<T> {
+            paginator: Mutex<Option<Paginator<T>>>,
+        }
+
+        impl<T: Send + 'static> StreamState<T> {
+            /// Polls the stored [Paginator], creating it with `init` on first use
+            pub(crate) fn poll_next_with(&mut self, cx: &mut Context<'_>, init: impl FnOnce() -> Paginator<T>) -> Poll<Option<Result<T, ApiError>>> {
+                match self.paginator.get_mut() {
+                    Ok(paginator) => paginator.get_or_insert_with(init).poll_next_unpin(cx),
+                    Err(_) => Poll::Ready(Some(Err(ApiError::UnknownError {}))),
+                }
+            }
+        }
+
+        impl<T> Default for StreamState<T> {
+            fn default() -> Self {
+                StreamState { paginator: Mutex::new(None) }
+            }
+        }
+
+        impl<T> Clone for StreamState<T> {
+            fn clone(&self) -> Self {
+                StreamState::default()
+            }
+        }
+
+        impl<T> fmt::Debug for StreamState<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let started = self.paginator.lock().map(|p| p.is_some()).unwrap_or(false);
+                f.debug_struct("StreamState").field("started", &started).finish()
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    pub mod responses {
+        use std::fmt::Debug;
+
+        use super::{posts::Post, users::User};
+        use serde_derive::{Deserialize, Serialize};
+        use serde_json::Value;
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        pub struct Login {
+            pub access_token: String,
+            pub user: User,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        pub struct NodeInfoSoftware {
+            pub name: String,
+            pub version: String,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        pub struct NodeInfo {
+            pub software: NodeInfoSoftware,
+            #[serde(default)]
+            pub protocols: Vec<String>,
+            #[serde(default)]
+            pub open_registrations: bool,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        pub struct DomainStatus {
+            pub domain: Option<String>,
+            #[serde(default)]
+            pub verified: bool,
+            #[serde(default)]
+            pub error_msg: Option<String>,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[serde(untagged)]
+        pub enum SearchHit {
+            Scored {
+                post: Post,
+                #[serde(default)]
+                score: Option<f32>,
+            },
+            Post(Post),
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        pub struct ErrorModel {
+            pub code: u16,
+            #[serde(default)]
+            pub error_msg: Option<String>,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        pub struct PostCount {
+            pub total_posts: Option<u64>,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all_fields = "camelCase"))]
+        #[serde(untagged)]
+        pub enum PageEnvelope<T> {
+            List(Vec<T>),
+            Posts {
+                posts: Vec<T>,
+                total_posts: Option<u64>,
+            },
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        pub struct ResponseModel {
+            pub code: u16,
+            pub data: Value,
+        }
+    }
+
+    #[doc(hidden)]
+    pub mod requests {
+        use serde_derive::{Deserialize, Serialize};
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        pub struct Login {
+            pub alias: String,
+            pub pass: String,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        pub struct PostToken {
+            pub token: String,
+        }
+    }
+
+    /// This module provides models related to [Collection]
+    pub mod collections {
+        use std::{
+            collections::HashMap,
+            fmt,
+            hash::{Hash, Hasher},
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        use chrono::{DateTime, Datelike, TimeZone, Utc};
+        use derive_builder::Builder;
+        use futures::{Stream, StreamExt};
+        use reqwest::Method;
+        use serde_derive::{Deserialize, Serialize};
+        use serde_repr::{Deserialize_repr, Serialize_repr};
+
+        use crate::api_client::{ApiError, Client, RequestError};
+
+        use super::{
+            meta::CollectionMeta,
+            pagination::{Page, Paginator, StreamState, DEFAULT_PAGE_SIZE},
+            posts::{
+                utils::{filter_posts_by_appearance, most_viewed, sort_posts_by_date, sort_posts_by_title, sort_posts_by_views},
+                embed_script, Post, PostAppearance, PostSummary,
+            },
+            responses::{DomainStatus, PostCount, SearchHit},
+        };
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        /// A struct describing a post to move into a collection
+        pub struct MovePost {
+            /// Post ID
+            pub id: String,
+
+            /// Post token, if post isn't owned
+            pub token: Option<String>,
+        }
+
+        impl MovePost {
+            /// Creates a new MovePost with just an ID
+            pub fn new(id: &str) -> Self {
+                MovePost {
+                    id: id.to_string(),
+                    token: None,
+                }
+            }
+
+            /// Creates a new MovePost with an ID and token
+            pub fn new_with_token(id: &str, token: &str) -> Self {
+                MovePost {
+                    id: id.to_string(),
+                    token: Some(token.to_string()),
+                }
+            }
+
+            /// Creates a new MovePost from a [Post], including its token if it was created anonymously
+            pub fn from_post(post: &Post) -> Self {
+                MovePost {
+                    id: post.id.clone(),
+                    token: post.token.clone(),
+                }
+            }
+
+            /// Creates a MovePost for each [Post] in a slice
+            pub fn from_posts(posts: &[Post]) -> Vec<Self> {
+                posts.iter().map(MovePost::from_post).collect()
+            }
+        }
+
+        #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        /// Verification status of a collection's custom domain (Write.as Pro only)
+        pub enum DomainVerificationStatus {
+            /// The domain points at Write.as and is serving the collection
+            Verified,
+
+            /// A domain is configured but hasn't been verified yet
+            Pending,
+
+            /// Verification of the configured domain failed
+            Failed {
+                /// Reason given by the server
+                reason: String,
+            },
+
+            /// No custom domain is configured
+            NotConfigured,
+
+            /// The server doesn't report domain verification status
+            Unknown,
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+        /// Part of a [Post] that matched a search query
+        pub enum MatchLocation {
+            /// The post title
+            Title,
+
+            /// The post body
+            Body,
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        /// A single [Post] matching a search query (see [Collection::search])
+        pub struct SearchResult {
+            /// Matching post
+            pub post: Post,
+
+            /// Relevance score assigned by the server, if searched server-side
+            pub relevance_score: Option<f32>,
+
+            /// Parts of the post containing the query
+            pub matched_in: Vec<MatchLocation>,
+        }
+
+        impl SearchResult {
+            fn new(post: Post, query: &str, relevance_score: Option<f32>) -> Self {
+                let query = query.to_lowercase();
+                let mut matched_in = Vec::new();
+                if post.title.as_ref().is_some_and(|t| t.to_lowercase().contains(&query)) {
+                    matched_in.push(MatchLocation::Title);
+                }
+                if post.body.to_lowercase().contains(&query) {
+                    matched_in.push(MatchLocation::Body);
+                }
+                SearchResult { post, relevance_score, matched_in }
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all_fields = "camelCase"))]
+        #[serde(untagged)]
+        /// Describes the result of a single post move operation
+        pub enum MoveResult {
+            /// Successful operation
+            Success { 
+                /// Operation status code
+                code: u32, 
+
+                /// Affected [Post]
+                post: Post 
+            },
+
+            /// Failed operation
+            Error { 
+                /// Operation status code
+                code: u32, 
+
+                /// Operation status text
+                error_msg: String 
+            },
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        /// A struct describing how to pin or unpin a post to a collection
+        pub struct PinPost {
+            /// Post ID
+            pub id: String,
+
+            #[serde(rename = "position", skip_serializing_if = "Option::is_none")]
+            /// Pin position (should not be used with `unpin`). Sent to the server as `position`, which is the field
+            /// name the API expects (older versions of this crate sent the misspelled `postion`, which was ignored).
+            pub postion: Option<u64>
+        }
+
+        impl PinPost {
+            /// Creates a new PinPost with an ID
+            pub fn new(id: &str) -> Self {
+                PinPost {
+                    id: id.to_string(),
+                    postion: None
+                }
+            }
+
+            /// Creates a new PinPost with an ID and a position
+            pub fn new_at_position(id: &str, position: u64) -> Self {
+                PinPost {
+                    id: id.to_string(),
+                    postion: Some(position),
+                }
+            }
+
+            /// Creates a new PinPost from a [Post], keeping its current pinned position (if known)
+            pub fn from_post(post: &Post) -> Self {
+                PinPost {
+                    id: post.id.clone(),
+                    postion: post.pinned_position,
+                }
+            }
+
+            /// Creates a PinPost for each [Post] in a slice
+            pub fn from_posts(posts: &[Post]) -> Vec<Self> {
+                posts.iter().map(PinPost::from_post).collect()
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        /// Orderings available to [Collection::get_posts_sorted]
+        pub enum SortOrder {
+            /// Oldest first (undated posts last)
+            DateAsc,
+
+            /// Newest first (undated posts last)
+            DateDesc,
+
+            /// Most viewed first
+            ViewsDesc,
+
+            /// Alphabetically by title (untitled posts first)
+            TitleAsc,
+        }
+
+        impl SortOrder {
+            /// Sorts posts in place according to this ordering
+            pub fn sort(&self, posts: &mut [Post]) {
+                match self {
+                    SortOrder::DateAsc => sort_posts_by_date(posts, true),
+                    SortOrder::DateDesc => sort_posts_by_date(posts, false),
+                    SortOrder::ViewsDesc => sort_posts_by_views(posts),
+                    SortOrder::TitleAsc => sort_posts_by_title(posts),
+                }
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all_fields = "camelCase"))]
+        #[serde(untagged)]
+        /// Describes the result of a single pin/unpin operation
+        pub enum PinResult {
+            /// Successful operation
+            Success { 
+                /// Operation status code
+                code: u32, 
+                /// Post ID
+                id: String 
+            },
+
+            /// Failed operation
+            Error { 
+                /// Operation status code
+                code: u32, 
+                /// Operation status text
+                error_msg: String 
+            },
+        }
+
+        fn pin_error(result: PinResult) -> ApiError {
+            match result {
+                PinResult::Error { code, error_msg } => ApiError::Request {
+                    error: RequestError {
+                        code: code as u16,
+                        reason: Some(error_msg),
+                    },
+                },
+                PinResult::Success { .. } => ApiError::UnknownError {},
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// A struct describing a single Collection entity
+        pub struct Collection {
+            #[serde(skip)]
+            ///
+            pub client: Option<Client>,
+            ///
+            pub alias: String,
+            ///
+            pub title: String,
+            ///
+            pub description: Option<String>,
+            ///
+            pub style_sheet: Option<String>,
+            ///
+            pub public: bool,
+            ///
+            pub views: Option<u64>,
+            /// Profile URL the collection links to with `rel="me"`, letting fediverse servers (eg. Mastodon) verify that the
+            /// profile & blog belong to the same person. `None` if no verification link has been configured.
+            pub verification_link: Option<String>,
+            ///
+            pub total_posts: Option<u64>,
+
+            #[serde(default)]
+            /// Full visibility level, if returned by the server (see [Collection::visibility])
+            pub visibility: Option<CollectionVisibility>,
+
+            #[serde(skip)]
+            #[doc(hidden)]
+            /// Pagination state used when iterating this collection's posts as a [Stream]
+            pub(crate) post_stream: StreamState<Post>,
+        }
+
+        /// Streams every [Post] in this collection, fetching [DEFAULT_PAGE_SIZE] posts at a time (see [Collection::stream_posts]).
+        /// Clones start over from the first page.
+        impl Stream for Collection {
+            type Item = Result<Post, ApiError>;
+
+            fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+                let collection = self.get_mut();
+                let mut state = std::mem::take(&mut collection.post_stream);
+                let result = state.poll_next_with(cx, || collection.stream_posts(DEFAULT_PAGE_SIZE));
+                collection.post_stream = state;
+                result
+            }
+        }
+
+        /// [Collection]s are compared by alias only
+        impl PartialEq for Collection {
+            fn eq(&self, other: &Self) -> bool {
+                self.alias == other.alias
+            }
+        }
+
+        impl Eq for Collection {}
+
+        /// Consistent with [PartialEq], hashes only the alias
+        impl Hash for Collection {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                self.alias.hash(state);
+            }
+        }
+
+        impl fmt::Display for Collection {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let visibility = match self.visibility() {
+                    CollectionVisibility::Public => "public",
+                    CollectionVisibility::Private => "private",
+                    CollectionVisibility::Unlisted => "unlisted",
+                    CollectionVisibility::Password => "password-protected",
+                };
+                write!(
+                    f,
+                    "{} [{}] - {} posts, {}",
+                    self.title,
+                    self.alias,
+                    self.total_posts.unwrap_or(0),
+                    visibility
+                )
+            }
+        }
+
+        impl Collection {
+            /// Creates a detached [Collection] with the given alias & title, and every other field empty.
+            /// Attach a [Client] with [Collection::with_client] to make requests with it.
+            pub fn new(alias: &str, title: &str) -> Self {
+                Collection {
+                    client: None,
+                    alias: alias.to_string(),
+                    title: title.to_string(),
+                    description: None,
+                    style_sheet: None,
+                    public: false,
+                    views: None,
+                    verification_link: None,
+                    total_posts: None,
+                    visibility: None,
+                    post_stream: StreamState::default(),
+                }
+            }
+
+            #[doc(hidden)]
+            pub fn with_client(&mut self, client: Client) -> Self {
+                self.client = Some(client);
+                self.clone()
+            }
+
+            /// Returns the public URL of this collection (under the client's base path, if any), if a [Client] is available
+            pub fn url(&self) -> Option<String> {
+                self.client
+                    .as_ref()?
+                    .api()
+                    .web_url(format!("/{}/", self.alias).as_str())
+                    .ok()
+                    .map(String::from)
+            }
+
+            /// Returns OpenGraph metadata describing this collection
+            pub fn opengraph_meta(&self) -> CollectionMeta {
+                CollectionMeta {
+                    og_title: self.title.clone(),
+                    og_description: self.description.clone(),
+                    og_url: self.url(),
+                    og_type: "website",
+                }
+            }
+
+            /// Returns a `<script>` tag that embeds one of this collection's posts (by slug) in another website.
+            /// Fails with [ApiError::UsageError] if no [Client] is available.
+            pub fn embed_code(&self, slug: &str) -> Result<String, ApiError> {
+                let client = self.client.as_ref().ok_or(ApiError::UsageError {})?;
+                embed_script(client, client.api().web_url(format!("/{}/{slug}", self.alias).as_str())?.as_str())
+            }
+
+            /// Returns the visibility level of this collection. If the server didn't return the full visibility level,
+            /// this is approximated from [Collection::public] (ie, [CollectionVisibility::Public] or [CollectionVisibility::Unlisted]).
+            pub fn visibility(&self) -> CollectionVisibility {
+                match self.visibility.clone() {
+                    Some(visibility) => visibility,
+                    None if self.public => CollectionVisibility::Public,
+                    None => CollectionVisibility::Unlisted,
+                }
+            }
+
+            /// Creates a [CollectionUpdateBuilder] with defaults set
+            #[must_use = "this returns a CollectionUpdateBuilder, which does nothing until built & sent"]
+            pub fn build_update(&self) -> CollectionUpdateBuilder {
+                CollectionUpdateBuilder::default()
+                    .alias(Some(self.alias.clone()))
+                    .client(self.client.clone())
+                    .clone()
+            }
+            
+            /// Updates a collection from an existing [CollectionUpdate]. A [no-op](CollectionUpdate::no_op) update returns this collection without making any request.
+            pub async fn update(&self, update: CollectionUpdate) -> Result<Collection, ApiError> {
+                if update.no_op() {
+                    return Ok(self.clone());
+                }
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .post::<Collection, CollectionUpdate>(
+                            format!("/collections/{}", self.alias).as_str(),
+                            Some(update.clone()),
+                        )
+                        .await
+                        .and_then(|mut p| Ok(p.with_client(client.clone())))
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            fn empty_update(&self) -> CollectionUpdate {
+                CollectionUpdate {
+                    client: self.client.clone(),
+                    alias: Some(self.alias.clone()),
+                    title: None,
+                    description: None,
+                    style_sheet: None,
+                    script: None,
+                    visibility: None,
+                    pass: None,
+                    mathjax: None,
+                    domain: None,
+                    new_alias: None,
+                }
+            }
+
+            /// Changes the alias (and optionally the title) of this collection. On success, `self` is updated so that subsequent calls use the new alias.
+            pub async fn rename(&mut self, new_alias: String, new_title: Option<String>) -> Result<Collection, ApiError> {
+                let mut update = self.empty_update();
+                update.new_alias = Some(new_alias.clone());
+                update.title = new_title;
+                let mut renamed = self.update(update).await?;
+                renamed.alias = new_alias;
+                *self = renamed;
+                Ok(self.clone())
+            }
+
+            /// Sets the visibility level of this collection
+            pub async fn set_visibility(&self, visibility: CollectionVisibility) -> Result<Collection, ApiError> {
+                let mut update = self.empty_update();
+                update.visibility = Some(visibility);
+                self.update(update).await
+            }
+
+            /// Makes this collection public
+            pub async fn make_public(&self) -> Result<Collection, ApiError> {
+                self.set_visibility(CollectionVisibility::Public).await
+            }
+
+            /// Makes this collection private
+            pub async fn make_private(&self) -> Result<Collection, ApiError> {
+                self.set_visibility(CollectionVisibility::Private).await
+            }
+
+            /// Makes this collection unlisted
+            pub async fn make_unlisted(&self) -> Result<Collection, ApiError> {
+                self.set_visibility(CollectionVisibility::Unlisted).await
+            }
+
+            /// Protects this collection with a password
+            pub async fn set_password(&self, pass: &str) -> Result<Collection, ApiError> {
+                let mut update = self.empty_update();
+                update.visibility = Some(CollectionVisibility::Password);
+                update.pass = Some(pass.to_string());
+                self.update(update).await
+            }
+
+            /// Returns the collection description rendered as sanitized HTML. Without the `markdown` feature, the raw Markdown is returned HTML-escaped.
+            pub fn description_as_html(&self) -> Option<String> {
+                #[cfg(feature = "markdown")]
+                {
+                    self.description.as_deref().map(super::posts::utils::render_markdown)
+                }
+
+                #[cfg(not(feature = "markdown"))]
+                {
+                    self.description.as_deref().map(super::posts::utils::escape_html)
+                }
+            }
+
+            /// Checks whether a [verification link](Collection::verification_link) has been configured. This only indicates
+            /// that verification was set up; whether the linked profile accepted it can't be determined through the API.
+            pub fn is_verified(&self) -> bool {
+                self.verification_link.is_some()
+            }
+
+            /// Returns the configured [verification link](Collection::verification_link), if any
+            pub fn verification_link_display(&self) -> Option<&str> {
+                self.verification_link.as_deref()
+            }
+
+            /// Checks the verification status of this collection's custom domain (Write.as only).
+            /// Returns [DomainVerificationStatus::Unknown] if the server doesn't expose the status endpoint.
+            pub async fn verify_domain(&self) -> Result<DomainVerificationStatus, ApiError> {
+                match self.client.clone() {
+                    Some(client) if client.is_write_as() => {
+                        match client
+                            .api()
+                            .get::<DomainStatus>(format!("/collections/{}/domain", self.alias).as_str())
+                            .await
+                        {
+                            Ok(DomainStatus { domain: None, .. }) => Ok(DomainVerificationStatus::NotConfigured),
+                            Ok(DomainStatus { verified: true, .. }) => Ok(DomainVerificationStatus::Verified),
+                            Ok(DomainStatus { error_msg: Some(reason), .. }) => Ok(DomainVerificationStatus::Failed { reason }),
+                            Ok(_) => Ok(DomainVerificationStatus::Pending),
+                            Err(e) if e.is_not_found() => Ok(DomainVerificationStatus::Unknown),
+                            Err(e) => Err(e),
+                        }
+                    }
+                    _ => Err(ApiError::UsageError {}),
+                }
+            }
+
+            /// Associates a custom domain with this collection (Write.as only)
+            pub async fn configure_domain(&self, domain: &str) -> Result<Collection, ApiError> {
+                match self.client.clone() {
+                    Some(client) if client.is_write_as() => {
+                        let mut update = self.empty_update();
+                        update.domain = Some(domain.to_string());
+                        self.update(update).await
+                    }
+                    _ => Err(ApiError::UsageError {}),
+                }
+            }
+
+            /// Returns this collection's custom stylesheet, if any
+            pub fn get_stylesheet(&self) -> Option<&str> {
+                self.style_sheet.as_deref()
+            }
+
+            /// Replaces this collection's custom stylesheet
+            pub async fn set_stylesheet(&self, css: &str) -> Result<Collection, ApiError> {
+                let mut update = self.empty_update();
+                update.style_sheet = Some(css.to_string());
+                self.update(update).await
+            }
+
+            /// Removes this collection's custom stylesheet. An empty stylesheet is sent, since `None` leaves it unchanged.
+            pub async fn clear_stylesheet(&self) -> Result<Collection, ApiError> {
+                self.set_stylesheet("").await
+            }
+
+            pub(crate) fn combined_stylesheet(&self, css: &str, prepend: bool) -> String {
+                match self.get_stylesheet() {
+                    Some(existing) if !existing.is_empty() => {
+                        if prepend {
+                            format!("{css}\n{existing}")
+                        } else {
+                            format!("{existing}\n{css}")
+                        }
+                    }
+                    _ => css.to_string(),
+                }
+            }
+
+            /// Appends CSS rules to this collection's stylesheet. Assumes `self` holds the current stylesheet (see [Collection::refresh]).
+            pub async fn append_stylesheet(&self, additional_css: &str) -> Result<Collection, ApiError> {
+                self.set_stylesheet(self.combined_stylesheet(additional_css, false).as_str()).await
+            }
+
+            /// Prepends CSS rules to this collection's stylesheet. Assumes `self` holds the current stylesheet (see [Collection::refresh]).
+            pub async fn prepend_stylesheet(&self, css: &str) -> Result<Collection, ApiError> {
+                self.set_stylesheet(self.combined_stylesheet(css, true).as_str()).await
+            }
+
+            /// Re-fetches this collection from the server, replacing `self` with the current data
+            pub async fn refresh(&mut self) -> Result<Collection, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    *self = client
+                        .api()
+                        .get::<Collection>(format!("/collections/{}", self.alias).as_str())
+                        .await?
+                        .with_client(client.clone());
+                    Ok(self.clone())
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Deletes this [Collection]
+            pub async fn delete(&self) -> Result<(), ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .delete(format!("/collections/{}", self.alias).as_str())
+                        .await
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Deletes every [Post] in this collection (fetched page by page beforehand), then the collection itself.
+            /// Failing post deletions don't stop the process, and the collection deletion is attempted regardless; if
+            /// anything fails, an [ApiError::BatchError] containing every error is returned.
+            pub async fn delete_with_posts(&self) -> Result<(), ApiError> {
+                let posts: Vec<Result<Post, ApiError>> = self.stream_posts(DEFAULT_PAGE_SIZE).collect().await;
+                let mut errors = Vec::new();
+                for post in posts.into_iter().collect::<Result<Vec<Post>, ApiError>>()? {
+                    if let Err(e) = post.delete().await {
+                        errors.push(e);
+                    }
+                }
+
+                if let Err(e) = self.delete().await {
+                    errors.push(e);
+                }
+
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(ApiError::BatchError { errors })
+                }
+            }
+
+            /// Deletes this collection, along with its posts if `force` is set (see [Collection::delete_with_posts]).
+            /// Without `force`, some servers refuse to delete collections that still contain posts.
+            pub async fn delete_or_clear(&self, force: bool) -> Result<(), ApiError> {
+                if force {
+                    self.delete_with_posts().await
+                } else {
+                    self.delete().await
+                }
+            }
+
+            /// Returns a [PostSummary] for every post in this collection. Asks the server to omit post bodies, though
+            /// servers that ignore the request still send them (in which case word counts are computed locally).
+            pub async fn get_post_summaries(&self) -> Result<Vec<PostSummary>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .get::<Vec<PostSummary>>(format!("/collections/{}/posts?summary=true", self.alias).as_str())
+                        .await
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns all [Post]s belonging to this collection
+            pub async fn get_posts(&self) -> Result<Vec<Post>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .get::<Vec<Post>>(format!("/collections/{}/posts", self.alias).as_str())
+                        .await
+                        .and_then(|mut v| {
+                            Ok(v.iter_mut()
+                                .map(|x| x.with_client(client.clone()))
+                                .collect())
+                        })
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns every [Post] in this collection, sorted client-side (the API doesn't support server-side sorting)
+            pub async fn get_posts_sorted(&self, order: SortOrder) -> Result<Vec<Post>, ApiError> {
+                let mut posts = self.get_posts().await?;
+                order.sort(&mut posts);
+                Ok(posts)
+            }
+
+            /// Returns every [Post] in this collection, most viewed first
+            pub async fn get_posts_sorted_by_views(&self) -> Result<Vec<Post>, ApiError> {
+                self.get_posts_sorted(SortOrder::ViewsDesc).await
+            }
+
+            /// Returns every [Post] in this collection, sorted by creation date
+            pub async fn get_posts_sorted_by_date(&self, ascending: bool) -> Result<Vec<Post>, ApiError> {
+                self.get_posts_sorted(if ascending { SortOrder::DateAsc } else { SortOrder::DateDesc }).await
+            }
+
+            /// Returns every [Post] in this collection, sorted by title
+            pub async fn get_posts_sorted_by_title(&self) -> Result<Vec<Post>, ApiError> {
+                self.get_posts_sorted(SortOrder::TitleAsc).await
+            }
+
+            /// Returns the current number of [Post]s in this collection, without downloading them.
+            /// Uses the `X-Total-Count` header if the server provides one, otherwise falls back to a minimal page request.
+            pub async fn get_post_count(&self) -> Result<u64, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let endpoint = format!("/collections/{}/posts", self.alias);
+                    if let Ok(headers) = client.api().head(endpoint.as_str()).await {
+                        if let Some(count) = headers
+                            .get("X-Total-Count")
+                            .and_then(|v| v.to_str().ok())
+                            .and_then(|v| v.parse::<u64>().ok())
+                        {
+                            return Ok(count);
+                        }
+                    }
+
+                    client
+                        .api()
+                        .get::<PostCount>(format!("{endpoint}?limit=1").as_str())
+                        .await
+                        .and_then(|v| v.total_posts.ok_or(ApiError::UnknownError {}))
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Checks whether this collection currently has no [Post]s. Always requests a single page holding at most one post,
+            /// since [total_posts](Collection::total_posts) may be stale.
+            pub async fn is_empty(&self) -> Result<bool, ApiError> {
+                self.posts_page(1, 1).await.map(|page| page.items.is_empty())
+            }
+
+            /// Returns a single [Page] of [Post]s belonging to this collection
+            pub async fn posts_page(&self, page: u64, per_page: u64) -> Result<Page<Post>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .get_paginated::<Post, _>(format!("/collections/{}/posts", self.alias).as_str(), (page, per_page))
+                        .await
+                        .map(|v| v.map(|mut x| x.with_client(client.clone())))
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns a [Paginator] over all [Post]s belonging to this collection, fetching `per_page` posts at a time
+            pub fn stream_posts(&self, per_page: u64) -> Paginator<Post> {
+                let collection = self.clone();
+                Paginator::new(per_page, move |page, per_page| {
+                    let collection = collection.clone();
+                    async move { collection.posts_page(page, per_page).await }
+                })
+            }
+
+            /// Returns the first [Post] in this collection matching a predicate. Posts are fetched page by page, stopping as soon as a match is found.
+            pub async fn find_post(&self, predicate: impl Fn(&Post) -> bool + Send) -> Result<Option<Post>, ApiError> {
+                let mut posts = self.stream_posts(DEFAULT_PAGE_SIZE);
+                while let Some(post) = posts.next().await {
+                    let post = post?;
+                    if predicate(&post) {
+                        return Ok(Some(post));
+                    }
+                }
+                Ok(None)
+            }
+
+            /// Returns all [Post]s in this collection matching a predicate
+            pub async fn find_posts(&self, predicate: impl Fn(&Post) -> bool + Send) -> Result<Vec<Post>, ApiError> {
+                let mut posts = self.stream_posts(DEFAULT_PAGE_SIZE);
+                let mut results = Vec::new();
+                while let Some(post) = posts.next().await {
+                    let post = post?;
+                    if predicate(&post) {
+                        results.push(post);
+                    }
+                }
+                Ok(results)
+            }
+
+            /// Searches this collection's [Post]s for a query (case-insensitive), using the server's search endpoint if available.
+            /// If the server doesn't support searching, every post is fetched and matched against its title & body client-side.
+            pub async fn search(&self, query: &str) -> Result<Vec<SearchResult>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let api = client.api();
+                    let request = api
+                        .request(format!("/collections/{}/search", self.alias).as_str(), Method::GET)?
+                        .query(&[("q", query)]);
+                    let response = request.send().await.or(Err(ApiError::ConnectionError {}))?;
+                    match api.extract_response::<Vec<SearchHit>>(response).await {
+                        Ok(hits) => Ok(hits
+                            .into_iter()
+                            .map(|hit| match hit {
+                                SearchHit::Scored { mut post, score } => {
+                                    SearchResult::new(post.with_client(client.clone()), query, score)
+                                }
+                                SearchHit::Post(mut post) => SearchResult::new(post.with_client(client.clone()), query, None),
+                            })
+                            .collect()),
+                        Err(e) if e.is_not_found() => Ok(self
+                            .find_posts(|p| !SearchResult::new(p.clone(), query, None).matched_in.is_empty())
+                            .await?
+                            .into_iter()
+                            .map(|p| SearchResult::new(p, query, None))
+                            .collect()),
+                        Err(e) => Err(e),
+                    }
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns all [Post]s in this collection created in `[start, end)`. Posts are filtered client-side, as the API has no date parameters.
+            pub async fn get_posts_between(&self, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Vec<Post>, ApiError> {
+                if start >= end {
+                    return Err(ApiError::ValidationError {
+                        errors: vec![format!("Start ({start}) must be before end ({end})")],
+                    });
+                }
+                self.find_posts(|p| p.created.is_some_and(|c| c >= start && c < end))
+                    .await
+            }
+
+            /// Returns all [Post]s in this collection created during a calendar month (UTC)
+            pub async fn get_posts_for_month(&self, year: i32, month: u32) -> Result<Vec<Post>, ApiError> {
+                let start = Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).single();
+                let end = if month == 12 {
+                    Utc.with_ymd_and_hms(year + 1, 1, 1, 0, 0, 0).single()
+                } else {
+                    Utc.with_ymd_and_hms(year, month + 1, 1, 0, 0, 0).single()
+                };
+                match (start, end) {
+                    (Some(start), Some(end)) => self.get_posts_between(start, end).await,
+                    _ => Err(ApiError::ValidationError {
+                        errors: vec![format!("Invalid month: {year}-{month}")],
+                    }),
+                }
+            }
+
+            /// Fetches every [Post] in this collection page by page, returning the `limit` most viewed (the API can't sort by views)
+            pub async fn most_viewed_posts(&self, limit: u64) -> Result<Vec<Post>, ApiError> {
+                let posts: Vec<Result<Post, ApiError>> = self.stream_posts(DEFAULT_PAGE_SIZE).collect().await;
+                Ok(most_viewed(posts.into_iter().collect::<Result<Vec<Post>, ApiError>>()?, limit))
+            }
+
+            /// Fetches every [Post] in this collection page by page, grouped by `(year, month)` of creation (UTC).
+            /// Undated posts are omitted, and each group is sorted chronologically.
+            pub async fn posts_by_month(&self) -> Result<HashMap<(i32, u32), Vec<Post>>, ApiError> {
+                self.archive_index().await.map(|index| index.by_month)
+            }
+
+            /// Fetches every [Post] in this collection page by page, grouped by year of creation (UTC).
+            /// Undated posts are omitted, and each group is sorted chronologically.
+            pub async fn posts_by_year(&self) -> Result<HashMap<i32, Vec<Post>>, ApiError> {
+                self.archive_index().await.map(|index| index.by_year)
+            }
+
+            /// Fetches every [Post] in this collection page by page, grouped by tag. Posts with several tags appear in each group.
+            pub async fn posts_by_tag(&self) -> Result<HashMap<String, Vec<Post>>, ApiError> {
+                self.archive_index().await.map(|index| index.by_tag)
+            }
+
+            /// Fetches every [Post] in this collection page by page, grouped by month, year & tag (see [ArchiveIndex])
+            pub async fn archive_index(&self) -> Result<ArchiveIndex, ApiError> {
+                let posts: Vec<Result<Post, ApiError>> = self.stream_posts(DEFAULT_PAGE_SIZE).collect().await;
+                Ok(ArchiveIndex::from_posts(posts.into_iter().collect::<Result<Vec<Post>, ApiError>>()?))
+            }
+
+            /// Returns the number of posts & total word count of this collection, warning if a large number of posts had to be fetched
+            async fn word_counts(&self) -> Result<(u64, u64), ApiError> {
+                let mut posts = self.stream_posts(DEFAULT_PAGE_SIZE);
+                let (mut count, mut words) = (0u64, 0u64);
+                while let Some(post) = posts.next().await {
+                    words += post?.word_count() as u64;
+                    count += 1;
+                    if count == 1001 {
+                        tracing::warn!(
+                            "Fetching more than 1000 posts from collection {} to count words; consider narrowing the scope with get_posts_between",
+                            self.alias
+                        );
+                    }
+                }
+                Ok((count, words))
+            }
+
+            /// Returns the total number of words across every [Post] in this collection
+            pub async fn total_word_count(&self) -> Result<u64, ApiError> {
+                self.word_counts().await.map(|(_, words)| words)
+            }
+
+            /// Returns the mean number of words per [Post] in this collection (`0.0` if it has no posts)
+            pub async fn average_post_length(&self) -> Result<f64, ApiError> {
+                self.word_counts().await.map(|(count, words)| {
+                    if count == 0 {
+                        0.0
+                    } else {
+                        words as f64 / count as f64
+                    }
+                })
+            }
+
+            /// Returns the [Post::content_hash] of every post in this collection, keyed by post ID
+            pub async fn content_hashes(&self) -> Result<HashMap<String, String>, ApiError> {
+                let mut posts = self.stream_posts(DEFAULT_PAGE_SIZE);
+                let mut hashes = HashMap::new();
+                while let Some(post) = posts.next().await {
+                    let post = post?;
+                    hashes.insert(post.id.clone(), post.content_hash());
+                }
+                Ok(hashes)
+            }
+
+            /// Returns all [Post]s belonging to this collection with a specific appearance. Filtering is done locally.
+            pub async fn get_posts_by_appearance(&self, font: PostAppearance) -> Result<Vec<Post>, ApiError> {
+                self.get_posts()
+                    .await
+                    .map(|posts| filter_posts_by_appearance(&posts, &font).into_iter().cloned().collect())
+            }
+
+            /// Returns aggregate statistics for this collection. WriteFreely doesn't provide a dedicated stats endpoint, so these are computed locally from [Collection::get_posts].
+            pub async fn statistics(&self) -> Result<CollectionStats, ApiError> {
+                self.get_posts().await.map(|posts| CollectionStats::from_posts(&posts))
+            }
+
+            /// Returns the most recently created [Post] in this collection, if any
+            pub async fn latest_post(&self) -> Result<Option<Post>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client.posts().latest(Some(&self.alias)).await
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns a single [Post] belonging to this collection
+            pub async fn get_post(&self, slug: String) -> Result<Post, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    client
+                        .api()
+                        .get::<Post>(format!("/collections/{}/posts/{}", self.alias, slug).as_str())
+                        .await
+                        .and_then(|mut v| Ok(v.with_client(client.clone())))
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Moves a set of [Post]s into this collection
+            pub async fn take_posts(
+                &self,
+                posts: &[MovePost],
+            ) -> Result<Vec<Result<MoveResult, MoveResult>>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let result = client
+                        .api()
+                        .post::<Vec<MoveResult>, &[MovePost]>(
+                            format!("/collections/{}/collect", self.alias).as_str(),
+                            Some(posts),
+                        )
+                        .await;
+                    match result {
+                        Ok(results) => Ok(results
+                            .iter()
+                            .map::<Result<MoveResult, MoveResult>, _>(|r| match r {
+                                MoveResult::Success { code, post } => Ok(MoveResult::Success {
+                                    code: code.clone(),
+                                    post: post.clone().with_client(client.clone()),
+                                }),
+                                MoveResult::Error { code, error_msg } => Err(MoveResult::Error {
+                                    code: code.clone(),
+                                    error_msg: error_msg.clone(),
+                                }),
+                            })
+                            .collect()),
+                        Err(e) => Err(e),
+                    }
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Returns all pinned [Post]s in this collection, in pinned order
+            pub async fn get_pinned_posts(&self) -> Result<Vec<Post>, ApiError> {
+                self.get_posts().await.map(|posts| {
+                    let mut pinned: Vec<Post> = posts
+                        .into_iter()
+                        .filter(|p| p.pinned_position.is_some())
+                        .collect();
+                    pinned.sort_by_key(|p| p.pinned_position);
+                    pinned
+                })
+            }
+
+            /// Unpins every currently pinned [Post] in this collection
+            pub async fn unpin_all_posts(&self) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
+                let ids: Vec<String> = self
+                    .get_pinned_posts()
+                    .await?
+                    .into_iter()
+                    .map(|p| p.id)
+                    .collect();
+                if ids.is_empty() {
+                    return Ok(vec![]);
+                }
+                self.unpin_posts(&ids).await
+            }
+
+            /// Pins a [Post] (by slug) at a specific position in this collection's pinned posts
+            pub async fn post_at_position(&self, slug: &str, position: u64) -> Result<PinResult, ApiError> {
+                let post = self.get_post(slug.to_string()).await?;
+                self.pin_posts(&[PinPost::new_at_position(&post.id, position)])
+                    .await
+                    .and_then(|v| match v.first() {
+                        Some(Ok(result)) | Some(Err(result)) => Ok(result.clone()),
+                        None => Err(ApiError::UnknownError {}),
+                    })
+            }
+
+            /// Pins a [Post] (by slug) above all other pinned posts
+            pub async fn pin_at_top(&self, slug: &str) -> Result<PinResult, ApiError> {
+                self.post_at_position(slug, 0).await
+            }
+
+            /// Pins a [Post] (by slug) below all other pinned posts, leaving the server to normalize the position
+            pub async fn pin_at_bottom(&self, slug: &str) -> Result<PinResult, ApiError> {
+                self.post_at_position(slug, u64::MAX).await
+            }
+
+            /// Replaces this collection's pinned [Post]s with the specified posts, pinned in the specified order.
+            /// Every currently pinned post is unpinned first; if any of the new pins is rejected, the previously pinned
+            /// posts are restored at their original positions (best-effort) & the error is returned.
+            pub async fn reorder_pinned_posts(&self, ordered_ids: &[&str]) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
+                let original = self.get_pinned_posts().await?;
+                if !original.is_empty() {
+                    self.unpin_posts(&original.iter().map(|p| p.id.clone()).collect::<Vec<String>>()).await?;
+                }
+                let pins: Vec<PinPost> = ordered_ids
+                    .iter()
+                    .enumerate()
+                    .map(|(i, id)| PinPost::new_at_position(id, i as u64 + 1))
+                    .collect();
+                let failure = match self.pin_posts(&pins).await {
+                    Ok(results) => match results.iter().find_map(|r| r.clone().err()) {
+                        Some(failed) => pin_error(failed),
+                        None => return Ok(results),
+                    },
+                    Err(e) => e,
+                };
+
+                let _ = self.unpin_posts(&ordered_ids.iter().map(|id| id.to_string()).collect::<Vec<String>>()).await;
+                if !original.is_empty() {
+                    let _ = self.pin_posts(&PinPost::from_posts(&original)).await;
+                }
+                Err(failure)
+            }
+
+            /// Moves an already-pinned [Post] (by ID) to a new position by unpinning it & re-pinning it at `new_position`.
+            /// Fails without re-pinning if the unpin operation is rejected. If re-pinning is rejected, the error is
+            /// returned & the post is left unpinned.
+            pub async fn move_pinned_post(&self, post_id: &str, new_position: u64) -> Result<PinResult, ApiError> {
+                match self.unpin_posts(&[post_id.to_string()]).await?.into_iter().next() {
+                    Some(Ok(_)) => (),
+                    Some(Err(result)) => return Err(pin_error(result)),
+                    None => return Err(ApiError::UnknownError {}),
+                }
+                match self.pin_posts(&[PinPost::new_at_position(post_id, new_position)]).await?.into_iter().next() {
+                    Some(Ok(result)) => Ok(result),
+                    Some(Err(result)) => Err(pin_error(result)),
+                    None => Err(ApiError::UnknownError {}),
+                }
+            }
+
+            /// Swaps the positions of two pinned [Post]s (by ID). Fails with [ApiError::UsageError] if either post isn't pinned.
+            /// If either post can't be re-pinned at its new position, both are pinned back at their original positions
+            /// & the error is returned.
+            pub async fn swap_pinned_posts(&self, id_a: &str, id_b: &str) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
+                let pinned = self.get_pinned_posts().await?;
+                let position = |id: &str| {
+                    pinned
+                        .iter()
+                        .find(|p| p.id == id)
+                        .and_then(|p| p.pinned_position)
+                        .ok_or(ApiError::UsageError {})
+                };
+                let (position_a, position_b) = (position(id_a)?, position(id_b)?);
+                self.unpin_posts(&[id_a.to_string(), id_b.to_string()]).await?;
+                let results = self
+                    .pin_posts(&[
+                        PinPost::new_at_position(id_a, position_b),
+                        PinPost::new_at_position(id_b, position_a),
+                    ])
+                    .await?;
+
+                if let Some(Err(failed)) = results.iter().find(|r| r.is_err()) {
+                    self.unpin_posts(&[id_a.to_string(), id_b.to_string()]).await?;
+                    self.pin_posts(&[
+                        PinPost::new_at_position(id_a, position_a),
+                        PinPost::new_at_position(id_b, position_b),
+                    ])
+                    .await?;
+                    return Err(pin_error(failed.clone()));
+                }
+                Ok(results)
+            }
+
+            /// Moves a set of [Post]s from another collection into this collection, after checking that they exist in the source collection
+            pub async fn take_posts_from(
+                &self,
+                source_alias: &str,
+                post_ids: &[&str],
+            ) -> Result<Vec<Result<MoveResult, MoveResult>>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let source = client.collections().get(source_alias).await?.get_posts().await?;
+                    if !post_ids.iter().all(|id| source.iter().any(|p| p.id == *id)) {
+                        return Err(ApiError::UsageError {});
+                    }
+
+                    let posts: Vec<MovePost> = post_ids.iter().map(|id| MovePost::new(id)).collect();
+                    self.take_posts(&posts).await
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Moves every [Post] from another collection into this collection in a single batch
+            pub async fn absorb(&self, source_alias: &str) -> Result<Vec<Result<MoveResult, MoveResult>>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let source = client.collections().get(source_alias).await?.get_posts().await?;
+                    let posts: Vec<MovePost> = source.iter().map(|p| MovePost::new(&p.id)).collect();
+                    self.take_posts(&posts).await
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Pins a set of [Post]s in this collection
+            pub async fn pin_posts(
+                &self,
+                posts: &[PinPost],
+            ) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let result = client
+                        .api()
+                        .post::<Vec<PinResult>, &[PinPost]>(
+                            format!("/collections/{}/pin", self.alias).as_str(),
+                            Some(posts),
+                        )
+                        .await;
+                    match result {
+                        Ok(results) => Ok(results
+                            .iter()
+                            .map::<Result<PinResult, PinResult>, _>(|r| match r {
+                                PinResult::Success { code, id } => Ok(PinResult::Success {
+                                    code: code.clone(),
+                                    id: id.clone()
+                                }),
+                                PinResult::Error { code, error_msg } => Err(PinResult::Error {
+                                    code: code.clone(),
+                                    error_msg: error_msg.clone(),
+                                }),
+                            })
+                            .collect()),
+                        Err(e) => Err(e),
+                    }
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+
+            /// Unpins a set of [Post]s from this collection
+            pub async fn unpin_posts(&self, posts: &[String]) -> Result<Vec<Result<PinResult, PinResult>>, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    let result = client
+                        .api()
+                        .post::<Vec<PinResult>, Vec<PinPost>>(
+                            format!("/collections/{}/unpin", self.alias).as_str(),
+                            Some(posts.iter().map(|v| PinPost::new(v.as_str())).collect()),
+                        )
+                        .await;
+                    match result {
+                        Ok(results) => Ok(results
+                            .iter()
+                            .map::<Result<PinResult, PinResult>, _>(|r| match r {
+                                PinResult::Success { code, id } => Ok(PinResult::Success {
+                                    code: code.clone(),
+                                    id: id.clone()
+                                }),
+                                PinResult::Error { code, error_msg } => Err(PinResult::Error {
+                                    code: code.clone(),
+                                    error_msg: error_msg.clone(),
+                                }),
+                            })
+                            .collect()),
+                        Err(e) => Err(e),
+                    }
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// A (possibly remote) collection followed by the authenticated user
+        pub struct FollowedCollection {
+            /// Public URL of the collection
+            pub url: String,
+
+            #[serde(default)]
+            /// Collection alias, if reported by the server
+            pub alias: Option<String>,
+
+            #[serde(default)]
+            /// Collection title, if reported by the server
+            pub title: Option<String>,
+
+            #[serde(default)]
+            /// Collection description, if reported by the server
+            pub description: Option<String>,
+
+            #[serde(default)]
+            /// When the collection was followed, if reported by the server
+            pub followed: Option<DateTime<Utc>>,
+        }
+
+        #[derive(Clone, Debug, Default)]
+        /// A collection's posts grouped for building archive pages (see [Collection::archive_index]).
+        /// Each group is sorted chronologically, and undated posts only appear in `by_tag`.
+        pub struct ArchiveIndex {
+            /// Posts grouped by `(year, month)` of creation (UTC)
+            pub by_month: HashMap<(i32, u32), Vec<Post>>,
+
+            /// Posts grouped by year of creation (UTC)
+            pub by_year: HashMap<i32, Vec<Post>>,
+
+            /// Posts grouped by tag
+            pub by_tag: HashMap<String, Vec<Post>>,
+        }
+
+        impl ArchiveIndex {
+            /// Groups a list of posts
+            pub fn from_posts(mut posts: Vec<Post>) -> Self {
+                posts.sort();
+                let mut index = ArchiveIndex::default();
+                for post in posts {
+                    for tag in &post.tags {
+                        index.by_tag.entry(tag.clone()).or_default().push(post.clone());
+                    }
+                    if let Some(created) = post.created {
+                        index.by_year.entry(created.year()).or_default().push(post.clone());
+                        index.by_month.entry((created.year(), created.month())).or_default().push(post);
+                    }
+                }
+                index
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// Aggregate statistics for a [Collection]
+        pub struct CollectionStats {
+            /// Total views across all posts
+            pub total_views: u64,
+
+            /// Total number of posts
+            pub total_posts: u64,
+
+            /// Number of unique visitors, if reported by the server
+            pub unique_visitors: Option<u64>,
+
+            /// Slug (or ID, if no slug is available) of the most viewed post
+            pub most_viewed_post: Option<String>,
+
+            /// Creation D/T of the most recent post
+            pub last_post_created: Option<DateTime<Utc>>,
+        }
+
+        impl CollectionStats {
+            /// Computes statistics locally from a set of [Post]s
+            pub fn from_posts(posts: &[Post]) -> Self {
+                CollectionStats {
+                    total_views: posts.iter().map(|p| p.views.unwrap_or(0)).sum(),
+                    total_posts: posts.len() as u64,
+                    unique_visitors: None,
+                    most_viewed_post: posts
+                        .iter()
+                        .filter(|p| p.views.is_some())
+                        .max_by_key(|p| p.views)
+                        .map(|p| p.slug.clone().unwrap_or(p.id.clone())),
+                    last_post_created: posts.iter().filter_map(|p| p.created).max(),
+                }
+            }
+        }
+
+        #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize_repr, Deserialize_repr)]
+        #[cfg_attr(feature = "strum", derive(strum::EnumIter))]
+        #[repr(u8)]
+        /// Enum describing a collection's visibility
+        pub enum CollectionVisibility {
+            ///
+            Unlisted = 0,
+            ///
+            Public = 1,
+            ///
+            Private = 2,
+            ///
+            Password = 4,
+        }
+
+        impl Default for CollectionVisibility {
+            /// Returns [CollectionVisibility::Unlisted], WriteFreely's documented default for new collections
+            fn default() -> Self {
+                CollectionVisibility::Unlisted
+            }
+        }
+
+        impl CollectionVisibility {
+            /// Returns an iterator over every [CollectionVisibility] variant
+            pub fn variants() -> impl Iterator<Item = CollectionVisibility> {
+                [
+                    CollectionVisibility::Unlisted,
+                    CollectionVisibility::Public,
+                    CollectionVisibility::Private,
+                    CollectionVisibility::Password,
+                ]
+                .into_iter()
+            }
+        }
+
+        #[derive(Clone, Debug, Serialize, Deserialize, Builder)]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all = "camelCase"))]
+        /// Struct describing a collection update
+        pub struct CollectionUpdate {
+            #[serde(skip_serializing)]
+            /// [Client] instance
+            pub client: Option<Client>,
+
+            #[serde(skip_serializing)]
+            /// Collection alias to update
+            pub alias: Option<String>,
+
+            #[builder(default)]
+            /// New title
+            pub title: Option<String>,
+
+            #[builder(default)]
+            /// New description
+            pub description: Option<String>,
+
+            #[builder(default)]
+            /// New style sheet
+            pub style_sheet: Option<String>,
+
+            #[builder(default)]
+            /// New script (Write.as only)
+            pub script: Option<String>,
+
+            #[builder(default)]
+            /// New visibility level
+            pub visibility: Option<CollectionVisibility>,
+
+            #[builder(default)]
+            /// New password (only [CollectionVisibility::Password])
+            pub pass: Option<String>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// Whether to enable Mathjax support. `None` leaves the current setting unchanged.
+            pub mathjax: Option<bool>,
+
+            #[serde(skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// New custom domain (Write.as Pro only)
+            pub domain: Option<String>,
+
+            #[serde(rename(serialize = "alias"), skip_deserializing, skip_serializing_if = "Option::is_none")]
+            #[builder(default)]
+            /// New alias, if renaming the collection (see [Collection::rename])
+            pub new_alias: Option<String>,
+        }
+
+        #[cfg(feature = "json-helpers")]
+        json_helpers!(Collection, CollectionUpdate);
+
+        impl CollectionUpdate {
+            /// Checks whether this update would make no changes (ie, every field is `None`)
+            pub fn no_op(&self) -> bool {
+                self.title.is_none()
+                    && self.description.is_none()
+                    && self.style_sheet.is_none()
+                    && self.script.is_none()
+                    && self.visibility.is_none()
+                    && self.pass.is_none()
+                    && self.mathjax.is_none()
+                    && self.domain.is_none()
+                    && self.new_alias.is_none()
+            }
+
+            /// Publish the update request to the server. If the update is a [no-op](CollectionUpdate::no_op), no update is sent,
+            /// but a GET request is still made to fetch the current collection (use [Collection::update] to skip the request entirely).
+            pub async fn update(&self) -> Result<Collection, ApiError> {
+                if let Some(client) = self.client.clone() {
+                    if let Some(alias) = self.alias.clone() {
+                        if self.no_op() {
+                            return client.collections().get(alias.as_str()).await;
+                        }
+                        client
+                            .api()
+                            .post::<Collection, CollectionUpdate>(
+                                format!("/collections/{}", alias).as_str(),
+                                Some(self.clone()),
+                            )
+                            .await
+                            .and_then(|mut p| Ok(p.with_client(client.clone())))
+                    } else {
+                        Err(ApiError::UsageError {})
+                    }
+                } else {
+                    Err(ApiError::UsageError {})
+                }
+            }
+        }
+    }
+
+    /// This module provides utilities for comparing snapshots of [Post](posts::Post)s & [Collection](collections::Collection)s, for sync tools
+    pub mod diff {
+        use std::collections::HashMap;
+
+        use super::{collections::Collection, posts::Post};
+
+        #[derive(Clone, Debug, Default)]
+        /// Changes between two snapshots of a list of [Post]s (see [diff_post_lists])
+        pub struct PostDiff {
+            /// Posts present only in the new snapshot
+            pub added: Vec<Post>,
+
+            /// IDs of posts present only in the old snapshot
+            pub removed: Vec<String>,
+
+            /// `(old, new)` pairs of posts whose [content hash](Post::content_hash) changed
+            pub modified: Vec<(Post, Post)>,
+        }
+
+        impl PostDiff {
+            /// Checks whether the snapshots were identical
+            pub fn is_empty(&self) -> bool {
+                self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+            }
+        }
+
+        #[derive(Clone, Debug, Default)]
+        /// Changes between two snapshots of a list of [Collection]s (see [diff_collection_lists])
+        pub struct CollectionDiff {
+            /// Collections present only in the new snapshot
+            pub added: Vec<Collection>,
+
+            /// Aliases of collections present only in the old snapshot
+            pub removed: Vec<String>,
+
+            /// `(old, new)` pairs of collections whose title, description, stylesheet, visibility or verification link changed
+            pub modified: Vec<(Collection, Collection)>,
+        }
+
+        impl CollectionDiff {
+            /// Checks whether the snapshots were identical
+            pub fn is_empty(&self) -> bool {
+                self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+            }
+        }
+
+        /// Compares two snapshots keyed by `key`, returning `(added, removed keys, modified pairs)` in snapshot order
+        fn diff_by<T: Clone>(old: &[T], new: &[T], key: impl Fn(&T) -> &str, changed: impl Fn(&T, &T) -> bool) -> (Vec<T>, Vec<String>, Vec<(T, T)>) {
+            let old_by_key: HashMap<&str, &T> = old.iter().map(|item| (key(item), item)).collect();
+            let new_by_key: HashMap<&str, &T> = new.iter().map(|item| (key(item), item)).collect();
+            let mut added = Vec::new();
+            let mut modified = Vec::new();
+            for item in new {
+                match old_by_key.get(key(item)) {
+                    None => added.push(item.clone()),
+                    Some(previous) if changed(previous, item) => modified.push(((*previous).clone(), item.clone())),
+                    Some(_) => {}
+                }
+            }
+            let removed = old
+                .iter()
+                .map(&key)
+                .filter(|k| !new_by_key.contains_key(k))
+                .map(str::to_string)
+                .collect();
+            (added, removed, modified)
+        }
+
+        /// Compares two snapshots of [Post]s by ID
+        pub fn diff_post_lists(old: &[Post], new: &[Post]) -> PostDiff {
+            let (added, removed, modified) = diff_by(old, new, |p| p.id.as_str(), |a, b| a.content_hash() != b.content_hash());
+            PostDiff { added, removed, modified }
+        }
+
+        /// Compares two snapshots of [Collection]s by alias
+        pub fn diff_collection_lists(old: &[Collection], new: &[Collection]) -> CollectionDiff {
+            let (added, removed, modified) = diff_by(
+                old,
+                new,
+                |c| c.alias.as_str(),
+                |a, b| {
+                    a.title != b.title
+                        || a.description != b.description
+                        || a.style_sheet != b.style_sheet
+                        || a.public != b.public
+                        || a.visibility != b.visibility
+                        || a.verification_link != b.verification_link
+                },
+            );
+            CollectionDiff { added, removed, modified }
+        }
+    }
+
+    /// This module provides models for parsing incoming webhook payloads, for WriteFreely forks that support them
+    pub mod webhook {
+        use hmac::{Hmac, Mac};
+        use serde_derive::{Deserialize, Serialize};
+        use sha2::Sha256;
+
+        use super::{collections::Collection, posts::Post, users::User};
+
+        #[derive(Clone, Debug, Serialize, Deserialize)]
+        #[serde(tag = "event", rename_all = "snake_case")]
+        #[cfg_attr(feature = "camel-case-api", serde(rename_all_fields = "camelCase"))]
+        /// An event delivered by a webhook, tagged by its `event` field (eg. `{"event": "post_deleted", "post_id": "..."}`)
+        pub enum WebhookEvent {
+            /// A [Post] was published
+            PostCreated {
+                /// The new post
+                post: Post
+            },
+
+            /// A [Post] was edited
+            PostUpdated {
+                /// The updated post
+                post: Post
+            },
+
+            /// A [Post] was deleted
+            PostDeleted {
+                /// ID of the deleted post
+                post_id: String
+            },
+
+            /// A [Collection] was created
+            CollectionCreated {
+                /// The new collection
+                collection: Collection
+            },
+
+            /// A [User] registered on the instance
+            UserRegistered {
+                /// The new user
+                user: User
+            },
+        }
+
+        /// Helpers for handling raw webhook requests
+        pub struct WebhookPayload;
+
+        impl WebhookPayload {
+            /// Parses a webhook request body into a [WebhookEvent]
+            pub fn from_json(s: &str) -> Result<WebhookEvent, serde_json::Error> {
+                serde_json::from_str(s)
+            }
+
+            /// Checks a request body against its hex-encoded HMAC-SHA256 signature (optionally prefixed with `sha256=`),
+            /// using a constant-time comparison. Returns `false` for malformed signatures.
+            pub fn verify_signature(body: &[u8], secret: &str, signature: &str) -> bool {
+                let signature = signature.trim();
+                let Ok(expected) = hex::decode(signature.strip_prefix("sha256=").unwrap_or(signature)) else {
+                    return false;
+                };
+                let Ok(mut mac) = Hmac::<Sha256>::new_from_slice(secret.as_bytes()) else {
+                    return false;
+                };
+                mac.update(body);
+                mac.verify_slice(&expected).is_ok()
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::api_client::{ApiError, Client};
+    use api_models::{
+        collections::{Collection, CollectionStats, CollectionUpdateBuilder, PinPost},
+        pagination::{Page, Paginator},
+        posts::{utils, Post, PostCreation, PostCreationBuilder, PostUpdate, PostUpdateBuilder},
+    };
+    use chrono::{DateTime, TimeZone, Utc};
+
+    macro_rules! aw {
+        ($e:expr) => {
+            tokio_test::block_on($e)
+        };
+    }
+
+    fn anon() -> Client {
+        Client::new("http://0.0.0.0:8080".to_string())
+    }
+
+    async fn anonymous_post() -> Post {
+        anon()
+            .posts()
+            .create("Anonymous test post".to_string())
+            .build()
+            .unwrap()
+            .publish()
+            .await
+            .unwrap()
+    }
+
+    fn post(id: &str, created: Option<DateTime<Utc>>, views: Option<u64>, title: Option<&str>) -> Post {
+        Post {
+            client: None,
+            id: id.to_string(),
+            slug: None,
+            appearance: None,
+            language: None,
+            rtl: false,
+            created,
+            title: title.map(|t| t.to_string()),
+            body: String::new(),
+            tags: vec![],
+            views,
+            collection: None,
+            token: None,
+            draft: None,
+            pinned_position: None,
+        }
+    }
+
+    fn ids(posts: &[Post]) -> Vec<&str> {
+        posts.iter().map(|p| p.id.as_str()).collect()
+    }
+
+    fn numbers(total: u64, per_page: u64) -> Paginator<u64> {
+        Paginator::new(per_page, move |page, per_page| async move {
+            let start = (page - 1) * per_page;
+            Ok(Page {
+                items: (start..total.min(start + per_page)).collect(),
+                page,
+                per_page,
+                total: Some(total),
+            })
+        })
+    }
+
+    #[test]
+    fn paginator_all_items() {
+        let items: Vec<u64> = aw!(numbers(7, 3).map(|v| v.unwrap()).collect());
+        assert_eq!(items, (0..7).collect::<Vec<u64>>());
+    }
+
+    #[test]
+    fn paginator_exact_pages() {
+        let items: Vec<u64> = aw!(numbers(6, 3).map(|v| v.unwrap()).collect());
+        assert_eq!(items.len(), 6);
+    }
+
+    #[test]
+    fn delete_anonymous_without_token() {
+        let mut post = aw!(anonymous_post());
+        let token = post.token.clone();
+        post.token = None;
+        assert!(aw!(post.delete()).is_err());
+
+        post.token = token;
+        assert!(aw!(post.delete()).is_ok());
+    }
+
+    #[test]
+    fn delete_anonymous_with_token() {
+        let post = aw!(anonymous_post());
+        assert!(post.token.is_some());
+        assert!(aw!(post.delete()).is_ok());
+    }
+
+    #[test]
+    fn sort_by_date() {
+        let day = |d| Some(Utc.with_ymd_and_hms(2024, 1, d, 0, 0, 0).unwrap());
+        let mut posts = vec![post("b", day(2), None, None), post("none", None, None, None), post("a", day(1), None, None), post("c", day(3), None, None)];
+
+        utils::sort_posts_by_date(&mut posts, true);
+        assert_eq!(ids(&posts), vec!["a", "b", "c", "none"]);
+
+        utils::sort_posts_by_date(&mut posts, false);
+        assert_eq!(ids(&posts), vec!["c", "b", "a", "none"]);
+    }
+
+    #[test]
+    fn sort_by_date_all_none() {
+        let mut posts = vec![post("a", None, None, None), post("b", None, None, None), post("c", None, None, None)];
+        utils::sort_posts_by_date(&mut posts, true);
+        assert_eq!(ids(&posts), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn sort_by_views() {
+        let mut posts = vec![post("none", None, None, None), post("low", None, Some(1), None), post("high", None, Some(10), None), post("zero", None, Some(0), None)];
+        utils::sort_posts_by_views(&mut posts);
+        assert_eq!(ids(&posts), vec!["high", "low", "none", "zero"]);
+    }
+
+    #[test]
+    fn sort_by_views_equal() {
+        let mut posts = vec![post("a", None, Some(5), None), post("b", None, Some(5), None), post("c", None, Some(7), None)];
+        utils::sort_posts_by_views(&mut posts);
+        assert_eq!(ids(&posts), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn sort_by_title() {
+        let mut posts = vec![post("b", None, None, Some("Beta")), post("none", None, None, None), post("a", None, None, Some("Alpha")), post("a2", None, None, Some("Alpha"))];
+        utils::sort_posts_by_title(&mut posts);
+        assert_eq!(ids(&posts), vec!["none", "a", "a2", "b"]);
+    }
+
+    #[test]
+    fn creation_builder_defaults() {
+        let creation = PostCreationBuilder::default().body("Body".to_string()).build().unwrap();
+        assert!(creation.title.is_none());
+        assert!(creation.collection.is_none());
+        assert!(PostCreationBuilder::default().title_required("Title".to_string()).build().is_err());
+    }
+
+    #[test]
+    fn creation_builder_body_default() {
+        let creation = PostCreationBuilder::default().body("Body".to_string()).body_if_empty_default("Default".to_string()).build().unwrap();
+        assert_eq!(creation.body, "Body");
+
+        let creation = PostCreationBuilder::default().body_if_empty_default("Default".to_string()).title_required("Title".to_string()).build().unwrap();
+        assert_eq!(creation.body, "Default");
+        assert_eq!(creation.title, Some("Title".to_string()));
+    }
+
+    #[test]
+    fn stats_from_posts() {
+        let day = |d| Some(Utc.with_ymd_and_hms(2024, 1, d, 0, 0, 0).unwrap());
+        let posts = vec![post("a", day(1), Some(3), None), post("b", day(5), Some(10), None), post("c", None, None, None)];
+        let stats = CollectionStats::from_posts(&posts);
+        assert_eq!(stats.total_views, 13);
+        assert_eq!(stats.total_posts, 3);
+        assert_eq!(stats.most_viewed_post, Some("b".to_string()));
+        assert_eq!(stats.last_post_created, day(5));
+
+        let empty = CollectionStats::from_posts(&[]);
+        assert_eq!(empty.total_posts, 0);
+        assert!(empty.most_viewed_post.is_none());
+    }
+
+    #[test]
+    fn post_urls() {
+        let mut anonymous = post("abc123", None, None, None);
+        assert!(anonymous.url().is_none());
+
+        anonymous = anonymous.with_client(anon());
+        assert_eq!(anonymous.url(), Some("http://0.0.0.0:8080/abc123".to_string()));
+        assert_eq!(anonymous.embed_code().unwrap(), "<script src=\"http://0.0.0.0:8080/js/embed.js?post=abc123\"></script>");
+        assert!(matches!(post("abc123", None, None, None).embed_code(), Err(ApiError::UsageError {})));
+
+        let mut blog = collection("blog", None);
+        assert!(blog.embed_code("hello").is_err());
+        blog.client = Some(anon());
+        assert_eq!(
+            blog.embed_code("hello world").unwrap(),
+            "<script src=\"http://0.0.0.0:8080/js/embed.js?post=http%3A%2F%2F0.0.0.0%3A8080%2Fblog%2Fhello%2520world\"></script>"
+        );
+
+        let subpath = Client::new_with_path("https://example.com", "/write").unwrap();
+        let mut scoped = post("abc123", None, None, None).with_client(subpath.clone());
+        assert_eq!(scoped.url(), Some("https://example.com/write/abc123".to_string()));
+        assert_eq!(scoped.embed_code().unwrap(), "<script src=\"https://example.com/write/js/embed.js?post=abc123\"></script>");
+        scoped.collection = Some(collection("blog", None));
+        scoped.slug = Some("hello".to_string());
+        assert_eq!(scoped.url(), Some("https://example.com/write/blog/hello".to_string()));
+
+        blog.client = Some(subpath);
+        assert_eq!(blog.url(), Some("https://example.com/write/blog/".to_string()));
+        assert_eq!(
+            blog.embed_code("hello").unwrap(),
+            "<script src=\"https://example.com/write/js/embed.js?post=https%3A%2F%2Fexample.com%2Fwrite%2Fblog%2Fhello\"></script>"
+        );
+    }
+
+    #[test]
+    fn post_excerpt() {
+        let mut p = post("a", None, None, Some("Title"));
+        p.body = "Hello   world,\nthis is a post".to_string();
+        assert_eq!(p.excerpt(100), "Hello world, this is a post");
+        assert_eq!(p.excerpt(12), "Hello world,…");
+        assert_eq!(p.excerpt(6), "Hello…");
+
+        let meta = p.opengraph_meta();
+        assert_eq!(meta.og_type, "article");
+        assert_eq!(meta.og_title, "Title");
+    }
+
+    #[test]
+    fn post_content_hash() {
+        let mut p = post("a", None, None, Some("Title"));
+        p.body = "Body".to_string();
+        let hash = p.content_hash();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, p.clone().content_hash());
+
+        p.views = Some(100);
+        assert_eq!(hash, p.content_hash());
+
+        p.title = Some("Other".to_string());
+        assert_ne!(hash, p.content_hash());
+    }
+
+    #[test]
+    #[cfg(feature = "camel-case-api")]
+    fn camel_case_keys() {
+        let user: api_models::users::User = serde_json::from_str(r#"{"username": "user", "displayName": "User"}"#).unwrap();
+        assert_eq!(user.display_name, Some("User".to_string()));
+        assert!(serde_json::to_string(&user).unwrap().contains("\"displayName\""));
+
+        let error: api_models::responses::ErrorModel = serde_json::from_str(r#"{"code": 401, "errorMsg": "Invalid access token"}"#).unwrap();
+        assert_eq!(error.error_msg, Some("Invalid access token".to_string()));
+        let stats: CollectionStats = serde_json::from_str(r#"{"totalViews": 3, "totalPosts": 1, "mostViewedPost": "a"}"#).unwrap();
+        assert_eq!(stats.most_viewed_post, Some("a".to_string()));
+    }
+
+    #[test]
+    #[cfg(not(feature = "camel-case-api"))]
+    fn snake_case_keys() {
+        let user: api_models::users::User = serde_json::from_str(r#"{"username": "user", "display_name": "User"}"#).unwrap();
+        assert_eq!(user.display_name, Some("User".to_string()));
+    }
+
+    fn collection(alias: &str, style_sheet: Option<&str>) -> Collection {
+        let mut collection = Collection::new(alias, alias);
+        collection.style_sheet = style_sheet.map(|s| s.to_string());
+        collection.public = true;
+        collection
+    }
+
+    #[test]
+    fn stylesheet_concatenation() {
+        let styled = collection("blog", Some("body { color: red; }"));
+        assert_eq!(
+            styled.combined_stylesheet("h1 { color: blue; }", false),
+            "body { color: red; }\nh1 { color: blue; }"
+        );
+        assert_eq!(
+            styled.combined_stylesheet("h1 { color: blue; }", true),
+            "h1 { color: blue; }\nbody { color: red; }"
+        );
+
+        let unstyled = collection("blog", None);
+        assert_eq!(unstyled.combined_stylesheet("h1 {}", false), "h1 {}");
+        assert_eq!(collection("blog", Some("")).combined_stylesheet("h1 {}", true), "h1 {}");
+    }
+
+    #[test]
+    fn refresh_requires_client() {
+        let mut c = collection("blog", Some("body {}"));
+        assert!(matches!(aw!(c.refresh()), Err(ApiError::UsageError {})));
+        assert_eq!(c.get_stylesheet(), Some("body {}"));
+    }
+
+    #[test]
+    fn collection_update_mathjax_unchanged() {
+        let update = CollectionUpdateBuilder::default()
+            .client(None)
+            .alias(Some("alias".to_string()))
+            .title(Some("Title".to_string()))
+            .build()
+            .unwrap();
+        assert!(update.mathjax.is_none());
+        assert!(!serde_json::to_string(&update).unwrap().contains("mathjax"));
+    }
+
+    #[test]
+    fn instance_info_from_nodeinfo() {
+        let nodeinfo: api_models::responses::NodeInfo = serde_json::from_str(
+            r#"{"version": "2.0", "software": {"name": "writefreely", "version": "0.15.0"}, "protocols": ["activitypub"], "openRegistrations": true}"#,
+        )
+        .unwrap();
+        let info = api_models::instance::InstanceInfo::from(nodeinfo);
+        assert_eq!(info.software, "writefreely");
+        assert_eq!(info.version, "0.15.0");
+        assert!(info.allow_registration);
+        assert!(info.federation_enabled);
+        assert!(info.max_post_size.is_none());
+    }
+
+    #[test]
+    fn post_collection_serializes_as_alias() {
+        let mut p = post("abc", None, None, None);
+        p.collection = serde_json::from_str(
+            r#"{"alias": "blog", "title": "Blog", "description": null, "style_sheet": null, "public": true, "views": null, "verification_link": null, "total_posts": null}"#,
+        )
+        .unwrap();
+        let value = serde_json::to_value(&p).unwrap();
+        assert_eq!(value["collection"], serde_json::json!("blog"));
+
+        let parsed: Post = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed.collection.unwrap().alias, "blog");
+    }
+
+    #[test]
+    fn post_display() {
+        let mut p = post(
+            "abc",
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap()),
+            None,
+            Some("Hello"),
+        );
+        p.body = "one two  three\nfour".to_string();
+        assert_eq!(p.word_count(), 4);
+        assert_eq!(p.to_string(), "Hello [abc] - 2024-03-01 (4 words)");
+
+        let untitled = post("def", None, None, None);
+        assert_eq!(untitled.to_string(), "Untitled [def] - unknown date (0 words)");
+    }
+
+    #[test]
+    fn creation_builder_collection_helpers() {
+        let creation = PostCreationBuilder::default()
+            .body("Hello world".to_string())
+            .in_collection("my-blog")
+            .build()
+            .unwrap();
+        assert_eq!(creation.collection.as_deref(), Some("my-blog"));
+
+        let creation = PostCreationBuilder::default()
+            .body("Hello world".to_string())
+            .in_collection("my-blog")
+            .anonymous()
+            .build()
+            .unwrap();
+        assert!(creation.collection.is_none());
+    }
+
+    #[test]
+    fn id_based_equality() {
+        use std::collections::HashSet;
+
+        let a = post("abc", None, Some(1), Some("Old"));
+        let b = post("abc", Some(Utc::now()), Some(5), Some("New"));
+        assert_eq!(a, b);
+        assert_ne!(a, post("def", None, Some(1), Some("Old")));
+        assert_eq!(HashSet::from([a, b]).len(), 1);
+
+        let mut styled = collection("blog", Some("body {}"));
+        styled.title = "Renamed".to_string();
+        assert_eq!(HashSet::from([collection("blog", None), styled, collection("other", None)]).len(), 2);
+    }
+
+    #[test]
+    fn post_template() {
+        use api_models::posts::{PostAppearance, PostTemplate};
+
+        let template = PostTemplate {
+            collection: Some("book".to_string()),
+            font: Some(PostAppearance::Mono),
+            lang: Some("de".to_string()),
+            rtl: None,
+        };
+        let posts: Vec<PostCreation> = template
+            .create_posts(vec![
+                ("Chapter one".to_string(), Some("One".to_string())),
+                ("Chapter two".to_string(), None),
+            ])
+            .iter()
+            .map(|b| b.build().unwrap())
+            .collect();
+        assert_eq!(posts.len(), 2);
+        assert_eq!((posts[0].body.as_str(), posts[0].title.as_deref()), ("Chapter one", Some("One")));
+        assert!(posts[1].title.is_none());
+        assert!(posts.iter().all(|p| p.collection.as_deref() == Some("book")
+            && p.font == Some(PostAppearance::Mono)
+            && p.lang.as_deref() == Some("de")));
+    }
+
+    #[test]
+    fn collection_verification_link() {
+        let mut c = collection("blog", None);
+        assert!(!c.is_verified());
+        assert!(c.verification_link_display().is_none());
+
+        c.verification_link = Some("https://mastodon.social/@me".to_string());
+        assert!(c.is_verified());
+        assert_eq!(c.verification_link_display(), Some("https://mastodon.social/@me"));
+    }
+
+    #[test]
+    fn creation_from_markdown() {
+        let creation = PostCreation::from_markdown_str("---\ntitle: \"Hello: world\"\nlang: fr\nfont: mono\nrtl: false\nblog: notes\ndate: 2024-03-01\ntags: [a, b]\n---\n\n# Body\n\nText")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(creation.title.as_deref(), Some("Hello: world"));
+        assert_eq!(creation.lang.as_deref(), Some("fr"));
+        assert_eq!(creation.font, Some(api_models::posts::PostAppearance::Mono));
+        assert_eq!(creation.rtl, Some(false));
+        assert_eq!(creation.collection.as_deref(), Some("notes"));
+        assert_eq!(creation.created, Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()));
+        assert_eq!(creation.body, "# Body\n\nText");
+
+        for plain in ["Just a body", "---\nunterminated: yes\nbody", "Intro\n---\ntitle: no"] {
+            assert_eq!(PostCreation::from_markdown_str(plain).unwrap().build().unwrap().body, plain);
+        }
+        assert!(PostCreation::from_markdown_str("---\nrtl: maybe\n---\nBody").is_err());
+
+        let path = std::env::temp_dir().join(format!("rust-freely-{}.md", std::process::id()));
+        std::fs::write(&path, "---\ntitle: From file\n---\nBody").unwrap();
+        let creation = aw!(PostCreation::from_file(&path)).unwrap().build().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((creation.title.as_deref(), creation.body.as_str()), (Some("From file"), "Body"));
+        assert!(aw!(PostCreation::from_file(&path)).is_err());
+    }
+
+    #[test]
+    fn creation_images() {
+        let mut creation = PostCreationBuilder::default().body("Hello".to_string()).build().unwrap();
+        assert_eq!(creation.with_images(&[]).body, "Hello");
+        assert_eq!(
+            creation.with_images(&["https://i.snap.as/a.png", "https://i.snap.as/b.png"]).body,
+            "Hello\n\n![](https://i.snap.as/a.png)\n![](https://i.snap.as/b.png)"
+        );
+
+        let mut creation = PostCreationBuilder::default().body("héllo".to_string()).build().unwrap();
+        assert_eq!(creation.insert_image_at("x.png", 2).body, "hé![](x.png)llo");
+        assert_eq!(creation.insert_image_at("y.png", 100).body, "hé![](x.png)llo![](y.png)");
+    }
+
+    #[test]
+    fn creation_builder_preview_image() {
+        let creation = PostCreationBuilder::default()
+            .body("Hello world".to_string())
+            .with_preview_image("https://example.com/image.png")
+            .build()
+            .unwrap();
+        assert_eq!(creation.preview_image_url.as_deref(), Some("https://example.com/image.png"));
+
+        let json = serde_json::to_value(&creation).unwrap();
+        let key = if cfg!(feature = "camel-case-api") { "previewImageUrl" } else { "preview_image_url" };
+        assert_eq!(json[key], "https://example.com/image.png");
+        assert!(json.get(if cfg!(feature = "camel-case-api") { "linkPreviewUrl" } else { "link_preview_url" }).is_none());
+    }
+
+    #[test]
+    fn post_conversions() {
+        let mut p = post("abc", None, None, Some("Title"));
+        p.body = "Body".to_string();
+        p.language = Some("en".to_string());
+        p.collection = Some(collection("blog", None));
+
+        let creation = PostCreation::from(p.clone());
+        assert_eq!(creation.body, "Body");
+        assert_eq!(creation.title.as_deref(), Some("Title"));
+        assert_eq!(creation.collection.as_deref(), Some("blog"));
+
+        let update = PostUpdate::from(p.clone());
+        assert_eq!(update.id, "abc");
+        assert_eq!(update.lang.as_deref(), Some("en"));
+
+        let built = PostUpdateBuilder::from(&p)
+            .body("New body".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(built.id, "abc");
+        assert_eq!(built.body, "New body");
+        assert_eq!(built.title.as_deref(), Some("Title"));
+    }
+
+    #[test]
+    fn enum_variants() {
+        use std::collections::HashSet;
+
+        use api_models::{collections::CollectionVisibility, posts::PostAppearance};
+
+        assert_eq!(PostAppearance::variants().count(), 5);
+        assert_eq!(PostAppearance::variants().collect::<HashSet<_>>().len(), 5);
+        assert_eq!(CollectionVisibility::variants().count(), 4);
+        assert_eq!(CollectionVisibility::variants().collect::<HashSet<_>>().len(), 4);
+    }
+
+    #[cfg(feature = "strum")]
+    #[test]
+    fn enum_variants_strum() {
+        use strum::IntoEnumIterator;
+
+        use api_models::{collections::CollectionVisibility, posts::PostAppearance};
+
+        assert!(PostAppearance::iter().eq(PostAppearance::variants()));
+        assert!(CollectionVisibility::iter().eq(CollectionVisibility::variants()));
+    }
+
+    #[cfg(feature = "json-helpers")]
+    #[test]
+    fn json_helpers() {
+        let p = post("abc", Some(Utc::now()), Some(4), Some("Title"));
+        let parsed = Post::from_json(p.to_json().unwrap().as_str()).unwrap();
+        assert_eq!((parsed.id, parsed.title, parsed.views), (p.id, p.title, p.views));
+
+        let c = collection("blog", Some("body {}"));
+        let parsed = Collection::from_json(c.to_json().unwrap().as_str()).unwrap();
+        assert_eq!((parsed.alias, parsed.style_sheet), (c.alias, c.style_sheet));
+
+        assert!(Post::from_json("{").is_err());
+    }
+
+    #[test]
+    fn validate_language() {
+        for valid in ["en", "fil", "en-US", "zh-Hant", "zh-Hant-TW", "es-419"] {
+            assert!(PostCreation::validate_language(valid), "{valid}");
+        }
+        for invalid in ["", "e", "en-", "en_US", "en US", "en-US!"] {
+            assert!(!PostCreation::validate_language(invalid), "{invalid}");
+        }
+
+        let creation = PostCreationBuilder::default()
+            .body("Hello".to_string())
+            .lang(Some("en_US".to_string()))
+            .build()
+            .unwrap();
+        assert!(matches!(creation.validate(), Err(ApiError::ValidationError { errors }) if errors.len() == 1));
+    }
+
+    #[cfg(feature = "markdown")]
+    #[test]
+    fn markdown_rendering() {
+        let mut p = post("abc", None, None, None);
+        p.body = "# Hello\n\n*world* <script>alert(1)</script>".to_string();
+        let html = p.body_as_html();
+        assert!(html.contains("<h1>Hello</h1>"));
+        assert!(html.contains("<em>world</em>"));
+        assert!(!html.contains("<script>"));
+
+        let mut c = collection("blog", None);
+        assert!(c.description_as_html().is_none());
+        c.description = Some("**bold**".to_string());
+        assert_eq!(c.description_as_html().unwrap().trim(), "<p><strong>bold</strong></p>");
+    }
+
+    #[test]
+    fn html_escaping() {
+        assert_eq!(utils::escape_html(r#"<a href="x">Tom & 'Jerry'</a>"#), "&lt;a href=&quot;x&quot;&gt;Tom &amp; &#39;Jerry&#39;&lt;/a&gt;");
+
+        #[cfg(not(feature = "markdown"))]
+        {
+            let mut p = post("abc", None, None, None);
+            p.body = "*world* <script>alert(1)</script>".to_string();
+            assert_eq!(p.body_as_html(), "*world* &lt;script&gt;alert(1)&lt;/script&gt;");
+        }
+    }
+
+    #[test]
+    fn body_length_check() {
+        let body = "a".repeat(10 * 1024 * 1024);
+        let creation = PostCreationBuilder::default()
+            .client(Some(anon().with_max_post_bytes(1024 * 1024)))
+            .body(body.clone())
+            .build()
+            .unwrap();
+        assert!(matches!(
+            creation.body_length_check(),
+            Err(ApiError::ValidationError { errors }) if errors == vec!["Body exceeds 1048576 bytes (actual: 10485760 bytes)".to_string()]
+        ));
+        assert!(creation.validate().is_err());
+
+        let unlimited = PostCreationBuilder::default()
+            .client(Some(anon()))
+            .body(body)
+            .build()
+            .unwrap();
+        assert!(unlimited.body_length_check().is_ok());
+    }
+
+    #[test]
+    fn move_and_pin_from_posts() {
+        use api_models::collections::{MovePost, PinPost};
+
+        let mut anonymous = post("abc", None, None, None);
+        anonymous.token = Some("token".to_string());
+        let mut pinned = post("def", None, None, None);
+        pinned.pinned_position = Some(2);
+
+        let moves = MovePost::from_posts(&[anonymous.clone(), pinned.clone()]);
+        assert_eq!(moves[0].token.as_deref(), Some("token"));
+        assert!(moves[1].token.is_none());
+
+        let pins = PinPost::from_posts(&[anonymous, pinned]);
+        assert_eq!(pins[0].postion, None);
+        assert_eq!(pins[1].postion, Some(2));
+    }
+
+    #[test]
+    fn post_slugs() {
+        assert_eq!(Post::generate_slug("Hello, World! It's 2024"), "hello-world-it-s-2024");
+        assert_eq!(Post::generate_slug("  --Already-slugged--  "), "already-slugged");
+
+        let mut p = post("abc123", None, None, Some("My First Post"));
+        assert_eq!(p.estimated_slug(), "my-first-post");
+        assert_eq!(p.final_slug(), "abc123");
+        p.slug = Some("my-first-post-2".to_string());
+        assert_eq!(p.final_slug(), "my-first-post-2");
+        assert_eq!(post("abc123", None, None, None).estimated_slug(), "abc123");
+    }
+
+    #[test]
+    fn update_no_op() {
+        let mut p = post("abc", None, None, Some("Title"));
+        p.body = "Body".to_string();
+        assert!(PostUpdate::from(p.clone()).no_op(&p));
+        assert!(!PostUpdateBuilder::from(&p).body("Changed".to_string()).build().unwrap().no_op(&p));
+        assert!(!PostUpdateBuilder::from(&p).draft(Some(true)).build().unwrap().no_op(&p));
+
+        let update = PostUpdateBuilder::from(&p)
+            .body("Changed".to_string())
+            .title(Some("Renamed".to_string()))
+            .build()
+            .unwrap();
+        assert!(update.body_changed(&p) && update.title_changed(&p) && !update.font_changed(&p));
+        assert!(update.any_changed(&p));
+        assert_eq!(update.changed_fields(&p), vec!["body", "title"]);
+
+        let untitled = PostUpdateBuilder::from(&p).title(None).font(None).lang(None).build().unwrap();
+        assert_eq!(p.title.as_deref(), Some("Title"));
+        assert!(!untitled.title_changed(&p) && !untitled.font_changed(&p) && !untitled.lang_changed(&p));
+        assert!(untitled.changed_fields(&p).is_empty() && untitled.no_op(&p));
+        assert!(PostUpdate::from(p.clone()).changed_fields(&p).is_empty());
+
+        let c = collection("blog", None);
+        let update = CollectionUpdateBuilder::default()
+            .client(None)
+            .alias(Some("blog".to_string()))
+            .build()
+            .unwrap();
+        assert!(update.no_op());
+        assert_eq!(aw!(c.update(update)).unwrap().alias, "blog");
+
+        let update = CollectionUpdateBuilder::default()
+            .client(None)
+            .alias(Some("blog".to_string()))
+            .mathjax(Some(true))
+            .build()
+            .unwrap();
+        assert!(!update.no_op());
+    }
+
+    #[test]
+    fn post_mentions_and_hashtags() {
+        let mut p = post("abc", None, None, None);
+        p.body = "# Heading\nHi @alice and @Bob@example.social. Mail me at me@example.com, @alice! #rust #Rust2024 #1 ##nope &#39;".to_string();
+        assert_eq!(p.mentions(), vec!["@alice", "@Bob@example.social"]);
+        assert!(p.has_mention("bob@EXAMPLE.social"));
+        assert!(p.has_mention("@Alice"));
+        assert!(!p.has_mention("me"));
+        assert_eq!(p.hashtags(), vec!["rust", "Rust2024"]);
+    }
+
+    #[test]
+    fn post_reading_level() {
+        use api_models::posts::{ReadingLevel, ReadingLevelCategory};
+
+        let mut p = post("abc", None, None, None);
+        p.body = "The cat sat on the mat.".to_string();
+        let level = p.reading_level();
+        assert!((level.flesch_kincaid_grade - -1.45).abs() < 0.01);
+        assert!((level.flesch_reading_ease - 116.145).abs() < 0.01);
+        assert_eq!(level.level, ReadingLevelCategory::Elementary);
+
+        p.body = "# Title\n\n**Comprehensive** institutional accountability necessitates considerable organizational [transparency](https://example.com).\n\n```\nlet code = ignored();\n```".to_string();
+        let level = p.reading_level();
+        assert!(level.flesch_kincaid_grade > 17.0);
+        assert_eq!(level.level, ReadingLevelCategory::Professional);
+        assert_eq!(
+            utils::strip_markdown("Read [the docs](https://example.com) *now*!"),
+            "Read the docs now!"
+        );
+
+        assert_eq!(ReadingLevel::from_text("").level, ReadingLevelCategory::Elementary);
+    }
+
+    #[test]
+    fn post_update_skips_none() {
+        let update = PostUpdateBuilder::default()
+            .client(None)
+            .id("abc".to_string())
+            .token(None)
+            .body("Body".to_string())
+            .title(None)
+            .font(None)
+            .lang(None)
+            .rtl(false)
+            .build()
+            .unwrap();
+        let value = serde_json::to_value(&update).unwrap();
+        let keys: Vec<&String> = value.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["body", "rtl"]);
+    }
+
+    #[test]
+    fn enum_defaults() {
+        use api_models::{collections::CollectionVisibility, posts::PostAppearance};
+
+        assert_eq!(PostAppearance::default(), PostAppearance::Serif);
+        assert_eq!(CollectionVisibility::default(), CollectionVisibility::Unlisted);
+        let creation = PostCreationBuilder::default()
+            .body("Hello".to_string())
+            .default_appearance()
+            .build()
+            .unwrap();
+        assert_eq!(creation.font, Some(PostAppearance::Serif));
+    }
+
+    #[test]
+    fn collection_rename_update() {
+        let update = CollectionUpdateBuilder::default()
+            .client(None)
+            .alias(Some("old".to_string()))
+            .new_alias(Some("new".to_string()))
+            .build()
+            .unwrap();
+        assert!(!update.no_op());
+        assert_eq!(serde_json::to_value(&update).unwrap()["alias"], "new");
+
+        let mut c = collection("old", None);
+        assert!(aw!(c.rename("new".to_string(), None)).is_err());
+        assert_eq!(c.alias, "old");
+    }
+
+    #[test]
+    fn posts_between_validation() {
+        let c = collection("blog", None);
+        let date = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        assert!(matches!(aw!(c.get_posts_between(date, date)), Err(ApiError::ValidationError { .. })));
+        assert!(matches!(aw!(c.get_posts_for_month(2024, 13)), Err(ApiError::ValidationError { .. })));
+        assert!(matches!(aw!(c.get_posts_for_month(2024, 12)), Err(ApiError::UsageError {})));
+    }
+
+    #[test]
+    fn post_age() {
+        use std::time::Duration;
+
+        let day = Duration::from_secs(24 * 60 * 60);
+        let old = post("old", Some(Utc::now() - chrono::Duration::days(10)), None, None);
+        assert!(old.age().unwrap() >= day * 10);
+        assert!(old.is_older_than(day * 7));
+        assert!(!old.is_newer_than(day * 7));
+
+        let new = post("new", Some(Utc::now() - chrono::Duration::hours(1)), None, None);
+        assert!(new.is_newer_than(day));
+        assert!(!new.is_older_than(day));
+
+        let undated = post("undated", None, None, None);
+        assert!(undated.age().is_none());
+        assert!(!undated.is_older_than(day) && !undated.is_newer_than(day));
+    }
+
+    #[test]
+    fn post_summary_parsing() {
+        use api_models::posts::PostSummary;
+
+        let full: PostSummary = serde_json::from_str(
+            r#"{"id": "abc", "slug": "hello", "title": "Hello", "body": "one two three", "tags": [], "collection": {"alias": "blog", "title": "Blog", "description": null, "style_sheet": null, "public": true, "views": null, "verification_link": null, "total_posts": null}}"#,
+        )
+        .unwrap();
+        assert_eq!(full.word_count, 3);
+        assert_eq!(full.collection.as_deref(), Some("blog"));
+
+        let key = if cfg!(feature = "camel-case-api") { "wordCount" } else { "word_count" };
+        let compact: PostSummary =
+            serde_json::from_str(format!(r#"{{"id": "def", "{key}": 42, "collection": "blog"}}"#).as_str()).unwrap();
+        assert_eq!(compact.word_count, 42);
+        assert_eq!(compact.collection.as_deref(), Some("blog"));
+
+        let mut p = post("ghi", None, Some(3), Some("Title"));
+        p.body = "a b".to_string();
+        let summary = PostSummary::from(&p);
+        assert_eq!((summary.word_count, summary.views), (2, Some(3)));
+    }
+
+    #[test]
+    fn webhook_payloads() {
+        use api_models::webhook::{WebhookEvent, WebhookPayload};
+
+        let key = if cfg!(feature = "camel-case-api") { "postId" } else { "post_id" };
+        let body = format!(r#"{{"event": "post_deleted", "{key}": "abc"}}"#);
+        assert!(matches!(
+            WebhookPayload::from_json(body.as_str()).unwrap(),
+            WebhookEvent::PostDeleted { post_id } if post_id == "abc"
+        ));
+        assert!(WebhookPayload::from_json(r#"{"event": "post_exploded"}"#).is_err());
+
+        // HMAC-SHA256("key", "The quick brown fox jumps over the lazy dog")
+        let body = b"The quick brown fox jumps over the lazy dog";
+        let signature = "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8";
+        assert!(WebhookPayload::verify_signature(body, "key", signature));
+        assert!(WebhookPayload::verify_signature(body, "key", format!("sha256={signature}").as_str()));
+        assert!(!WebhookPayload::verify_signature(body, "other", signature));
+        assert!(!WebhookPayload::verify_signature(body, "key", "not hex"));
+    }
+
+    #[test]
+    fn post_anchor_links() {
+        let mut p = post("abc", None, None, None);
+        p.body = [
+            "See [the docs](https://example.com/docs \"Docs\") and [my notes][notes], or [Notes].",
+            "![diagram](/img/diagram.png) Also https://rust-lang.org/learn. and <https://crates.io>",
+            "```",
+            "[ignored](https://example.com/ignored)",
+            "```",
+            "[notes]: notes",
+        ]
+        .join("\n");
+
+        let urls: Vec<(String, String)> = p.anchor_links(false);
+        assert_eq!(
+            urls,
+            vec![
+                ("the docs".to_string(), "https://example.com/docs".to_string()),
+                ("my notes".to_string(), "notes".to_string()),
+                ("Notes".to_string(), "notes".to_string()),
+                ("https://rust-lang.org/learn".to_string(), "https://rust-lang.org/learn".to_string()),
+                ("https://crates.io".to_string(), "https://crates.io".to_string()),
+            ]
+        );
+        assert_eq!(p.anchor_links(true)[3], ("diagram".to_string(), "/img/diagram.png".to_string()));
+
+        p.collection = Some(collection("blog", None));
+        p.client = Some(Client::new("https://example.com".to_string()));
+        let links = p.anchor_links(true);
+        assert_eq!(links[1].1, "https://example.com/blog/notes");
+        assert_eq!(links[3].1, "https://example.com/img/diagram.png");
+    }
+
+    #[test]
+    fn sort_orders() {
+        use api_models::collections::SortOrder;
+
+        let mut posts = vec![
+            post("a", Some(Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap()), Some(5), Some("Beta")),
+            post("b", None, Some(9), None),
+            post("c", Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()), None, Some("Alpha")),
+        ];
+        let ids = |posts: &[Post]| posts.iter().map(|p| p.id.clone()).collect::<Vec<String>>();
+
+        SortOrder::DateAsc.sort(&mut posts);
+        assert_eq!(ids(&posts), vec!["c", "a", "b"]);
+        SortOrder::DateDesc.sort(&mut posts);
+        assert_eq!(ids(&posts), vec!["a", "c", "b"]);
+        SortOrder::ViewsDesc.sort(&mut posts);
+        assert_eq!(ids(&posts), vec!["b", "a", "c"]);
+        SortOrder::TitleAsc.sort(&mut posts);
+        assert_eq!(ids(&posts), vec!["b", "c", "a"]);
+
+        assert!(matches!(aw!(collection("blog", None).get_posts_sorted_by_views()), Err(ApiError::UsageError {})));
+    }
+
+    #[test]
+    fn snapshot_diffs() {
+        use api_models::diff::{diff_collection_lists, diff_post_lists};
+
+        let old = vec![post("a", None, Some(1), Some("A")), post("b", None, None, Some("B")), post("c", None, None, None)];
+        let mut edited = post("b", None, None, Some("B"));
+        edited.body = "Edited".to_string();
+        let new = vec![post("a", None, Some(99), Some("A")), edited, post("d", None, None, None)];
+
+        let diff = diff_post_lists(&old, &new);
+        assert_eq!(diff.added.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(), vec!["d"]);
+        assert_eq!(diff.removed, vec!["c"]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!((diff.modified[0].0.body.as_str(), diff.modified[0].1.body.as_str()), ("", "Edited"));
+        assert!(diff_post_lists(&old, &old).is_empty());
+
+        let old = vec![collection("blog", None), collection("notes", None)];
+        let new = vec![collection("blog", Some("body {}")), collection("notes", None)];
+        let diff = diff_collection_lists(&old, &new);
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.modified[0].1.alias, "blog");
+    }
+
+    #[test]
+    fn post_ordering() {
+        use std::collections::BTreeSet;
+
+        let date = |day| Some(Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap());
+        let mut posts = vec![
+            post("undated", None, None, None),
+            post("c", date(3), None, None),
+            post("a", date(1), None, None),
+            post("b2", date(2), None, None),
+            post("b1", date(2), None, None),
+        ];
+        posts.sort();
+        assert_eq!(posts.iter().map(|p| p.id.as_str()).collect::<Vec<_>>(), vec!["a", "b1", "b2", "c", "undated"]);
+        assert_eq!(BTreeSet::from_iter(posts.clone()).len(), 5);
+        assert_eq!(posts.iter().max().unwrap().id, "undated");
+
+        let (first, copy, other) = (post("x", date(1), None, None), post("x", date(3), None, None), post("y", date(2), None, None));
+        assert!(first < other && other < copy && first < copy);
+        let mut shuffled = [copy.clone(), other.clone(), first.clone()];
+        shuffled.sort();
+        assert_eq!(shuffled.iter().map(|p| p.created).collect::<Vec<_>>(), vec![date(1), date(2), date(3)]);
+        assert_eq!(BTreeSet::from([first, copy, other]).len(), 3);
+    }
+
+    #[test]
+    fn archive_index() {
+        use api_models::collections::ArchiveIndex;
+
+        let date = |month, day| Some(Utc.with_ymd_and_hms(2024, month, day, 0, 0, 0).unwrap());
+        let mut posts = vec![
+            post("late", date(3, 20), None, None),
+            post("early", date(3, 1), None, None),
+            post("feb", date(2, 5), None, None),
+            post("undated", None, None, None),
+        ];
+        posts[0].tags = vec!["rust".to_string(), "async".to_string()];
+        posts[3].tags = vec!["rust".to_string()];
+
+        let ids = |posts: &[Post]| posts.iter().map(|p| p.id.clone()).collect::<Vec<String>>();
+        let index = ArchiveIndex::from_posts(posts);
+        assert_eq!(ids(&index.by_month[&(2024, 3)]), vec!["early", "late"]);
+        assert_eq!(ids(&index.by_month[&(2024, 2)]), vec!["feb"]);
+        assert_eq!(ids(&index.by_year[&2024]), vec!["feb", "early", "late"]);
+        assert_eq!(ids(&index.by_tag["rust"]), vec!["late", "undated"]);
+        assert_eq!(ids(&index.by_tag["async"]), vec!["late"]);
+        assert_eq!(index.by_month.len(), 2);
+    }
+
+    #[test]
+    fn most_viewed() {
+        let posts = vec![post("a", None, Some(3), None), post("b", None, None, None), post("c", None, Some(10), None)];
+        let top: Vec<String> = utils::most_viewed(posts.clone(), 2).into_iter().map(|p| p.id).collect();
+        assert_eq!(top, vec!["c", "a"]);
+        assert_eq!(utils::most_viewed(posts.clone(), 10).len(), 3);
+        assert!(utils::most_viewed(posts, 0).is_empty());
+    }
+
+    #[test]
+    fn creation_tokens() {
+        let creation = PostCreationBuilder::default().body("Hi".to_string()).generate_token().build().unwrap();
+        let token = creation.token.clone().unwrap();
+        assert_eq!(token.len(), 32);
+        assert!(token.chars().all(|c| c.is_ascii_alphanumeric()));
+        let other = PostCreationBuilder::default().body("Hi".to_string()).generate_token().build().unwrap();
+        assert_ne!(other.token, creation.token);
+
+        let creation = PostCreationBuilder::default().body("Hi".to_string()).with_token("mine".to_string()).build().unwrap();
+        assert_eq!(serde_json::to_value(&creation).unwrap()["token"], "mine");
+    }
+
+    #[test]
+    fn models_skip_client() {
+        let client = Client::new("http://0.0.0.0:8080".to_string());
+        let mut post = post("a", None, None, None);
+        post.client = Some(client.clone());
+        let mut parent = collection("blog", None);
+        parent.client = Some(client);
+        post.collection = Some(parent.clone());
+        assert!(serde_json::to_value(&post).unwrap().get("client").is_none());
+        assert!(serde_json::to_value(&parent).unwrap().get("client").is_none());
+    }
+
+    #[test]
+    fn pin_post_serializes_position() {
+        let pin = serde_json::to_value(PinPost::new_at_position("abc", 2)).unwrap();
+        assert_eq!(pin, serde_json::json!({"id": "abc", "position": 2}));
+        assert_eq!(serde_json::to_value(PinPost::new("abc")).unwrap(), serde_json::json!({"id": "abc"}));
+    }
 }
@@ -4,7 +4,7 @@
 //!  - Token & Username/Password authentication
 //!  - Most post management endpoints
 //!  - All collection endpoints
-//!  - All user endpoints except channels
+//!  - All user endpoints, including Write.as channels
 
 #![warn(missing_docs)]
 mod client;
@@ -13,5 +13,5 @@ pub use client::api_models;
 pub use client::api_wrapper;
 pub use client::api_handlers;
 
-pub use client::api_client::{Client, ApiError, Auth};
+pub use client::api_client::{Client, ClientBuilder, ApiError, Auth, AuthFailReason, AuthParseError, TokenStore, WRITEAS_BASE_URL};
 pub use client::api_models::{collections::{Collection, CollectionVisibility}, posts::{Post, PostAppearance}, users::User};